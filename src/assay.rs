@@ -0,0 +1,236 @@
+//! BioAssay (AID) summaries and per-compound bioactivity tables.
+
+use serde::Deserialize;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct AssaySummariesEnvelope {
+    #[serde(rename = "AssaySummaries")]
+    pub summaries: AssaySummaries,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct AssaySummaries {
+    #[serde(rename = "AssaySummary", default)]
+    pub summaries: Vec<AssaySummary>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct AssayTarget {
+    #[serde(rename = "Accession")]
+    pub accession: Option<String>,
+    #[serde(rename = "Name")]
+    pub name: Option<String>,
+    #[serde(rename = "GeneID")]
+    pub gene_id: Option<u32>,
+}
+
+/// Overview of one BioAssay from `assay/aid/<aid>/summary`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct AssaySummary {
+    #[serde(rename = "AID")]
+    pub aid: u32,
+    #[serde(rename = "SourceName")]
+    pub source_name: Option<String>,
+    #[serde(rename = "Name")]
+    pub name: Option<String>,
+    #[serde(rename = "Description", default)]
+    pub description: Vec<String>,
+    #[serde(rename = "Method")]
+    pub method: Option<String>,
+    #[serde(rename = "Target", default)]
+    pub targets: Vec<AssayTarget>,
+    #[serde(rename = "CIDCountAll")]
+    pub cid_count_all: Option<u32>,
+    #[serde(rename = "CIDCountActive")]
+    pub cid_count_active: Option<u32>,
+    #[serde(rename = "CIDCountInactive")]
+    pub cid_count_inactive: Option<u32>,
+    #[serde(rename = "SIDCountAll")]
+    pub sid_count_all: Option<u32>,
+    #[serde(rename = "SIDCountActive")]
+    pub sid_count_active: Option<u32>,
+}
+
+impl fmt::Display for AssaySummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Assay Information:")?;
+        writeln!(f, "---------------------")?;
+        writeln!(f, "AID: {}", self.aid)?;
+        writeln!(f, "Name: {}", self.name.as_deref().unwrap_or("Unknown"))?;
+        writeln!(f, "Source: {}", self.source_name.as_deref().unwrap_or("Unknown"))?;
+        writeln!(f, "Method: {}", self.method.as_deref().unwrap_or("Unknown"))?;
+        writeln!(f, "Tested CIDs: {:?}", self.cid_count_all)?;
+        writeln!(f, "Active CIDs: {:?}", self.cid_count_active)?;
+        writeln!(f, "Inactive CIDs: {:?}", self.cid_count_inactive)?;
+        writeln!(f, "Targets:")?;
+        for target in &self.targets {
+            writeln!(
+                f,
+                "  {} ({}), GeneID: {:?}",
+                target.name.as_deref().unwrap_or("Unknown"),
+                target.accession.as_deref().unwrap_or("no accession"),
+                target.gene_id
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Outcome of testing one substance in one assay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActivityOutcome {
+    Active,
+    Inactive,
+    Inconclusive,
+    Unspecified,
+    Probe,
+}
+
+impl FromStr for ActivityOutcome {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "active" => Ok(ActivityOutcome::Active),
+            "inactive" => Ok(ActivityOutcome::Inactive),
+            "inconclusive" => Ok(ActivityOutcome::Inconclusive),
+            "unspecified" | "" => Ok(ActivityOutcome::Unspecified),
+            "probe" => Ok(ActivityOutcome::Probe),
+            other => Err(format!("unknown activity outcome '{}'", other)),
+        }
+    }
+}
+
+impl fmt::Display for ActivityOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            ActivityOutcome::Active => "Active",
+            ActivityOutcome::Inactive => "Inactive",
+            ActivityOutcome::Inconclusive => "Inconclusive",
+            ActivityOutcome::Unspecified => "Unspecified",
+            ActivityOutcome::Probe => "Probe",
+        };
+        f.write_str(name)
+    }
+}
+
+/// One row of `compound/.../assaysummary`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bioactivity {
+    pub aid: u32,
+    pub sid: Option<u32>,
+    pub cid: Option<u32>,
+    pub outcome: ActivityOutcome,
+    pub target_accession: Option<String>,
+    pub target_gene_id: Option<u32>,
+    /// Reported activity in micromolar, e.g. an IC50.
+    pub activity_value: Option<f64>,
+    pub activity_name: Option<String>,
+    pub assay_name: Option<String>,
+    pub assay_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct TableEnvelope {
+    #[serde(rename = "Table")]
+    pub table: Table,
+}
+
+/// PubChem's generic column/row table, with every cell as a string.
+#[derive(Debug, Deserialize)]
+pub(crate) struct Table {
+    #[serde(rename = "Columns")]
+    columns: TableColumns,
+    #[serde(rename = "Row", default)]
+    rows: Vec<TableRow>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TableColumns {
+    #[serde(rename = "Column")]
+    names: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TableRow {
+    #[serde(rename = "Cell")]
+    cells: Vec<String>,
+}
+
+impl Table {
+    /// Converts rows to [`Bioactivity`] by column name; rows without a numeric AID are skipped.
+    pub(crate) fn into_bioactivities(self) -> Vec<Bioactivity> {
+        let column = |name: &str| self.columns.names.iter().position(|column| column == name);
+        let aid = column("AID");
+        let sid = column("SID");
+        let cid = column("CID");
+        let outcome = column("Activity Outcome");
+        let accession = column("Target Accession");
+        let gene_id = column("Target GeneID");
+        let value = column("Activity Value [uM]");
+        let activity_name = column("Activity Name");
+        let assay_name = column("Assay Name");
+        let assay_type = column("Assay Type");
+        self.rows
+            .iter()
+            .filter_map(|row| {
+                let cell = |index: Option<usize>| {
+                    index
+                        .and_then(|i| row.cells.get(i))
+                        .map(|cell| cell.trim())
+                        .filter(|cell| !cell.is_empty())
+                };
+                Some(Bioactivity {
+                    aid: cell(aid)?.parse().ok()?,
+                    sid: cell(sid).and_then(|v| v.parse().ok()),
+                    cid: cell(cid).and_then(|v| v.parse().ok()),
+                    outcome: cell(outcome)
+                        .and_then(|v| v.parse().ok())
+                        .unwrap_or(ActivityOutcome::Unspecified),
+                    target_accession: cell(accession).map(String::from),
+                    target_gene_id: cell(gene_id).and_then(|v| v.parse().ok()),
+                    activity_value: cell(value).and_then(|v| v.parse().ok()),
+                    activity_name: cell(activity_name).map(String::from),
+                    assay_name: cell(assay_name).map(String::from),
+                    assay_type: cell(assay_type).map(String::from),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_assay_summary() {
+        let envelope: AssaySummariesEnvelope =
+            serde_json::from_str(include_str!("../tests/data/assay_summary_aid_1000.json")).unwrap();
+        let summary = &envelope.summaries.summaries[0];
+        assert_eq!(summary.aid, 1000);
+        assert_eq!(summary.method.as_deref(), Some("confirmatory"));
+        assert_eq!(summary.cid_count_active, Some(47));
+        assert_eq!(summary.targets[0].gene_id, Some(5742));
+    }
+
+    #[test]
+    fn parses_bioactivity_table() {
+        let envelope: TableEnvelope =
+            serde_json::from_str(include_str!("../tests/data/assaysummary_cid_2244.json")).unwrap();
+        let rows = envelope.table.into_bioactivities();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].aid, 1000);
+        assert_eq!(rows[0].outcome, ActivityOutcome::Active);
+        assert_eq!(rows[0].activity_value, Some(1.67));
+        assert_eq!(rows[0].activity_name.as_deref(), Some("IC50"));
+        assert_eq!(rows[0].sid, Some(135196));
+        assert_eq!(rows[0].assay_type.as_deref(), Some("Confirmatory"));
+        assert_eq!(rows[1].outcome, ActivityOutcome::Inactive);
+        assert_eq!(rows[1].activity_value, None);
+        assert_eq!(rows[2].outcome, ActivityOutcome::Inconclusive);
+        assert_eq!(rows[2].target_accession, None);
+    }
+}
@@ -0,0 +1,97 @@
+//! Per-identifier bookkeeping for batch runs.
+
+use crate::error::PcPugError;
+use std::error::Error;
+use std::fmt;
+
+/// Outcome of a batch run, so one bad identifier doesn't hide the rest of the results.
+#[derive(Debug, Default)]
+pub struct BatchSummary {
+    pub succeeded: Vec<String>,
+    pub not_found: Vec<String>,
+    /// Identifier and error message for every other failure.
+    pub failed: Vec<(String, String)>,
+}
+
+impl BatchSummary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the outcome for `identifier`, classifying PubChem not-found faults separately.
+    pub fn record<T>(&mut self, identifier: &str, result: &Result<T, Box<dyn Error>>) {
+        match result {
+            Ok(_) => self.succeeded.push(identifier.to_string()),
+            Err(err) => self.record_error(identifier, err.as_ref()),
+        }
+    }
+
+    pub fn record_error(&mut self, identifier: &str, err: &(dyn Error + 'static)) {
+        match err.downcast_ref::<PcPugError>() {
+            Some(pc_err) if pc_err.is_not_found() => self.not_found.push(identifier.to_string()),
+            _ => self.failed.push((identifier.to_string(), err.to_string())),
+        }
+    }
+
+    pub fn total(&self) -> usize {
+        self.succeeded.len() + self.not_found.len() + self.failed.len()
+    }
+
+    pub fn has_failures(&self) -> bool {
+        !self.not_found.is_empty() || !self.failed.is_empty()
+    }
+
+    /// True when at least one identifier was processed and none succeeded.
+    pub fn all_failed(&self) -> bool {
+        self.total() > 0 && self.succeeded.is_empty()
+    }
+}
+
+impl fmt::Display for BatchSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Batch Summary:")?;
+        writeln!(f, "---------------------")?;
+        writeln!(f, "Succeeded: {}", self.succeeded.len())?;
+        writeln!(f, "Not Found: {}", self.not_found.len())?;
+        for identifier in &self.not_found {
+            writeln!(f, "  {}", identifier)?;
+        }
+        writeln!(f, "Errors: {}", self.failed.len())?;
+        for (identifier, message) in &self.failed {
+            writeln!(f, "  {}: {}", identifier, message)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_outcomes() {
+        let mut summary = BatchSummary::new();
+        summary.record::<()>("aspirin", &Ok(()));
+        let not_found: Box<dyn Error> = Box::new(PcPugError::from_response(404, ""));
+        summary.record::<()>("notacompound", &Err(not_found));
+        let busy: Box<dyn Error> = Box::new(PcPugError::from_response(503, ""));
+        summary.record::<()>("caffeine", &Err(busy));
+        summary.record::<()>("ibuprofen", &Err("plot failed".into()));
+
+        assert_eq!(summary.succeeded, vec!["aspirin"]);
+        assert_eq!(summary.not_found, vec!["notacompound"]);
+        assert_eq!(summary.failed.len(), 2);
+        assert_eq!(summary.failed[1], ("ibuprofen".to_string(), "plot failed".to_string()));
+        assert_eq!(summary.total(), 4);
+        assert!(summary.has_failures());
+        assert!(!summary.all_failed());
+    }
+
+    #[test]
+    fn all_failed_requires_at_least_one_entry() {
+        let mut summary = BatchSummary::new();
+        assert!(!summary.all_failed());
+        summary.record::<()>("x", &Err("boom".into()));
+        assert!(summary.all_failed());
+    }
+}
@@ -0,0 +1,266 @@
+//! On-disk response cache keyed by a hash of the request.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// How long an entry is served before it is fetched again.
+pub const DEFAULT_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Sidecar written next to each cached body.
+#[derive(Debug, Serialize, Deserialize)]
+struct EntryMeta {
+    /// `METHOD url`, for humans inspecting the directory.
+    request: String,
+    /// Seconds since the Unix epoch.
+    stored_at: u64,
+    size: u64,
+}
+
+/// Content-addressed store of raw PubChem responses.
+///
+/// Each response lives in `<dir>/<sha256>.body` with a `<sha256>.json` sidecar recording the request
+/// and when it was stored. The hash covers the method, URL and request body, so POSTed SMILES and
+/// list requests are cached like GETs. Only successful, finished responses are stored; `Waiting`
+/// ListKey answers never are.
+#[derive(Debug, Clone)]
+pub struct ResponseCache {
+    dir: PathBuf,
+    ttl: Option<Duration>,
+    offline: bool,
+}
+
+impl ResponseCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        ResponseCache {
+            dir: dir.into(),
+            ttl: Some(DEFAULT_TTL),
+            offline: false,
+        }
+    }
+
+    /// Replaces the default one-day TTL; `None` keeps entries forever.
+    pub fn ttl(mut self, ttl: Option<Duration>) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Serves only from the cache, ignoring the TTL, and fails requests it has no entry for.
+    pub fn offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    /// Hex SHA-256 of the method, URL and body.
+    pub(crate) fn key(method: &str, url: &str, body: &[u8]) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(method.as_bytes());
+        hasher.update(b"\n");
+        hasher.update(url.as_bytes());
+        hasher.update(b"\n");
+        hasher.update(body);
+        hasher.finalize().iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    fn body_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.body", key))
+    }
+
+    fn meta_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", key))
+    }
+
+    /// The cached body for `key`, if present and (unless offline) younger than the TTL.
+    pub(crate) fn get(&self, key: &str) -> Option<Vec<u8>> {
+        let meta = read_meta(&self.meta_path(key))?;
+        if !self.offline && self.is_expired(&meta, now()) {
+            return None;
+        }
+        fs::read(self.body_path(key)).ok()
+    }
+
+    pub(crate) fn put(&self, key: &str, request: &str, body: &[u8]) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        fs::write(self.body_path(key), body)?;
+        let meta = EntryMeta {
+            request: request.to_string(),
+            stored_at: now(),
+            size: body.len() as u64,
+        };
+        // Sidecar last: an entry only counts once both files exist.
+        fs::write(self.meta_path(key), serde_json::to_vec(&meta)?)
+    }
+
+    fn is_expired(&self, meta: &EntryMeta, now: u64) -> bool {
+        self.ttl
+            .is_some_and(|ttl| now.saturating_sub(meta.stored_at) >= ttl.as_secs())
+    }
+
+    /// Every complete entry as `(key, meta)`; a missing directory is an empty cache.
+    fn entries(&self) -> io::Result<Vec<(String, EntryMeta)>> {
+        let listing = match fs::read_dir(&self.dir) {
+            Ok(listing) => listing,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err),
+        };
+        let mut entries = Vec::new();
+        for file in listing {
+            let path = file?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            let Some(key) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            if let Some(meta) = read_meta(&path) {
+                entries.push((key.to_string(), meta));
+            }
+        }
+        Ok(entries)
+    }
+
+    pub fn stats(&self) -> io::Result<CacheStats> {
+        let now = now();
+        let mut stats = CacheStats::default();
+        for (_, meta) in self.entries()? {
+            stats.entries += 1;
+            stats.bytes += meta.size;
+            if self.is_expired(&meta, now) {
+                stats.expired += 1;
+            }
+        }
+        Ok(stats)
+    }
+
+    /// Deletes expired entries, or every entry when `all` is set; returns how many were removed.
+    pub fn prune(&self, all: bool) -> io::Result<usize> {
+        let now = now();
+        let mut removed = 0;
+        for (key, meta) in self.entries()? {
+            if all || self.is_expired(&meta, now) {
+                remove_if_present(&self.meta_path(&key))?;
+                remove_if_present(&self.body_path(&key))?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+}
+
+/// Totals reported by [`ResponseCache::stats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub entries: usize,
+    pub expired: usize,
+    pub bytes: u64,
+}
+
+impl fmt::Display for CacheStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} entries ({} expired), {} bytes",
+            self.entries, self.expired, self.bytes
+        )
+    }
+}
+
+fn read_meta(path: &Path) -> Option<EntryMeta> {
+    let bytes = fs::read(path).ok()?;
+    serde_json::from_slice(&bytes).ok()
+}
+
+fn remove_if_present(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("pcpug-cache-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    /// Rewrites an entry's timestamp as if it had been stored `age` ago.
+    fn age_entry(cache: &ResponseCache, key: &str, age: Duration) {
+        let path = cache.meta_path(key);
+        let mut meta = read_meta(&path).unwrap();
+        meta.stored_at -= age.as_secs();
+        fs::write(path, serde_json::to_vec(&meta).unwrap()).unwrap();
+    }
+
+    #[test]
+    fn key_covers_method_url_and_body() {
+        let get = ResponseCache::key("GET", "https://x/compound/cid/2244/JSON", b"");
+        assert_eq!(get.len(), 64);
+        assert_eq!(get, ResponseCache::key("GET", "https://x/compound/cid/2244/JSON", b""));
+        assert_ne!(get, ResponseCache::key("POST", "https://x/compound/cid/2244/JSON", b""));
+        assert_ne!(
+            ResponseCache::key("POST", "https://x/compound/smiles/JSON", b"smiles=CCO"),
+            ResponseCache::key("POST", "https://x/compound/smiles/JSON", b"smiles=CCN")
+        );
+    }
+
+    #[test]
+    fn serves_fresh_entries_and_expires_old_ones() {
+        let dir = scratch_dir("ttl");
+        let cache = ResponseCache::new(&dir).ttl(Some(Duration::from_secs(60)));
+        assert_eq!(cache.get("abc"), None);
+        cache.put("abc", "GET https://x", b"body").unwrap();
+        assert_eq!(cache.get("abc").as_deref(), Some(&b"body"[..]));
+
+        age_entry(&cache, "abc", Duration::from_secs(120));
+        assert_eq!(cache.get("abc"), None);
+        assert_eq!(cache.clone().offline(true).get("abc").as_deref(), Some(&b"body"[..]));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn reports_stats_and_prunes_expired_entries() {
+        let dir = scratch_dir("prune");
+        let cache = ResponseCache::new(&dir).ttl(Some(Duration::from_secs(60)));
+        assert_eq!(cache.stats().unwrap(), CacheStats::default());
+        cache.put("fresh", "GET https://x/1", b"12345").unwrap();
+        cache.put("stale", "GET https://x/2", b"123").unwrap();
+        age_entry(&cache, "stale", Duration::from_secs(3600));
+        assert_eq!(
+            cache.stats().unwrap(),
+            CacheStats {
+                entries: 2,
+                expired: 1,
+                bytes: 8
+            }
+        );
+
+        assert_eq!(cache.prune(false).unwrap(), 1);
+        assert!(!cache.body_path("stale").exists());
+        assert_eq!(cache.stats().unwrap().entries, 1);
+        assert_eq!(cache.prune(true).unwrap(), 1);
+        assert_eq!(cache.stats().unwrap().entries, 0);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,659 @@
+//! HTTP client for the PubChem PUG REST service.
+
+use crate::assay::{AssaySummariesEnvelope, AssaySummary, Bioactivity, TableEnvelope};
+use crate::cache::ResponseCache;
+use crate::compound::{parse_compounds, Compound, RecordType};
+use crate::error::{Fault, PcPugError, Result};
+use crate::format::OutputFormat;
+use crate::information::{
+    group_descriptions, ConformerIds, DescriptionEntry, Description, InformationListEnvelope, NameType, Synonyms, XrefEntry, XrefKind,
+    Xrefs,
+};
+use crate::listkey::{IdentifierListEnvelope, PollPolicy, Waiting};
+use crate::namespace::{encode_path_segment, Namespace};
+use crate::properties::{property_list, CompoundProperties, Property, PropertyTableEnvelope};
+use crate::retry::RetryPolicy;
+use crate::search::{SearchKind, SearchOptions};
+use crate::substance::{PcSubstances, Substance};
+use crate::throttle::{RateLimit, RateLimiter, ThrottlingStatus, THROTTLING_HEADER};
+use crate::view::{
+    ExperimentalProperties, GhsClassification, ViewEnvelope, ViewRecord, DEFAULT_VIEW_BASE_URL, EXPERIMENTAL_HEADING,
+    GHS_HEADING,
+};
+use futures::stream::{self, Stream, StreamExt};
+use std::collections::HashMap;
+use tokio::time::Instant;
+use std::sync::Arc;
+
+/// Root of the public PUG REST service.
+pub const DEFAULT_BASE_URL: &str = "https://pubchem.ncbi.nlm.nih.gov/rest/pug";
+
+/// Identifiers per list request in [`PubChemClient::get_compounds_batch`]; keeps full
+/// records for a chunk well inside PubChem's response size and time limits.
+pub const DEFAULT_CHUNK_SIZE: usize = 100;
+
+/// One list request's outcome in [`PubChemClient::get_compounds_batch`].
+#[derive(Debug)]
+pub struct BatchChunk {
+    /// The chunk's identifiers, in input order.
+    pub identifiers: Vec<String>,
+    /// The record found for each identifier, in the same order, or why the whole request failed.
+    pub compounds: Result<Vec<Option<Compound>>>,
+}
+
+/// Fetches and decodes PubChem records.
+///
+/// The base URL is configurable so tests can point the client at a local mock server.
+/// PUG View requests go to a sibling root (`.../rest/pug_view`), see [`PubChemClient::view_base_url`].
+/// Clones share one rate limiter, so the PubChem request budget holds across them.
+#[derive(Debug, Clone)]
+pub struct PubChemClient {
+    http: reqwest::Client,
+    base_url: String,
+    view_base_url: String,
+    rate_limiter: Arc<RateLimiter>,
+    retry_policy: RetryPolicy,
+    poll_policy: PollPolicy,
+    record_type: RecordType,
+    cache: Option<ResponseCache>,
+}
+
+impl Default for PubChemClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PubChemClient {
+    /// Client for the public PUG REST and PUG View services.
+    pub fn new() -> Self {
+        Self::with_base_url(DEFAULT_BASE_URL).view_base_url(DEFAULT_VIEW_BASE_URL)
+    }
+
+    /// Uses `base_url` for PUG REST. A base ending in `/pug` sends PUG View requests to the
+    /// matching `/pug_view` root; any other base is used as the PUG View root as well.
+    pub fn with_base_url(base_url: impl Into<String>) -> Self {
+        let base_url = base_url.into().trim_end_matches('/').to_string();
+        let view_base_url = if let Some(root) = base_url.strip_suffix("/pug") {
+            format!("{}/pug_view", root)
+        } else {
+            base_url.clone()
+        };
+        PubChemClient {
+            http: reqwest::Client::new(),
+            base_url,
+            view_base_url,
+            rate_limiter: Arc::new(RateLimiter::new(RateLimit::default())),
+            retry_policy: RetryPolicy::default(),
+            poll_policy: PollPolicy::default(),
+            record_type: RecordType::default(),
+            cache: None,
+        }
+    }
+
+    /// Sends PUG View requests to `view_base_url` instead of the root derived from the PUG REST base.
+    pub fn view_base_url(mut self, view_base_url: impl Into<String>) -> Self {
+        self.view_base_url = view_base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Serves repeated requests from `cache` and stores new responses in it.
+    pub fn cache(mut self, cache: ResponseCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Requests 2D (default) or 3D coordinates in full compound records.
+    pub fn record_type(mut self, record_type: RecordType) -> Self {
+        self.record_type = record_type;
+        self
+    }
+
+    /// Replaces the default ListKey polling schedule (1s doubling to 10s, 120s deadline).
+    pub fn poll_policy(mut self, policy: PollPolicy) -> Self {
+        self.poll_policy = policy;
+        self
+    }
+
+    /// Replaces the default retry policy (3 attempts, exponential backoff with jitter, 30s timeout).
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Replaces the default 5 requests/second, 400 requests/minute budget.
+    pub fn rate_limit(mut self, limit: RateLimit) -> Self {
+        self.rate_limiter = Arc::new(RateLimiter::new(limit));
+        self
+    }
+
+    /// Most recent status PubChem reported in `X-Throttling-Control`, if any.
+    pub fn throttling_status(&self) -> Option<ThrottlingStatus> {
+        self.rate_limiter.status()
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Adds `record_type=3d` to full-record requests when 3D coordinates were asked for.
+    fn with_record_type(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match self.record_type {
+            RecordType::TwoD => request,
+            RecordType::ThreeD => request.query(&[("record_type", self.record_type.as_str())]),
+        }
+    }
+
+    fn compound_request(&self, namespace: Namespace, identifier: &str, operation: &str) -> reqwest::RequestBuilder {
+        self.input_request("compound", namespace, identifier, operation)
+    }
+
+    /// Builds `<prefix>/<namespace>/<identifier>/<operation>`, moving the identifier into
+    /// a POST body for namespaces whose values can't live in a URL path.
+    fn input_request(&self, prefix: &str, namespace: Namespace, identifier: &str, operation: &str) -> reqwest::RequestBuilder {
+        if namespace.uses_post() {
+            let url = format!("{}/{}/{}/{}", self.base_url, prefix, namespace, operation);
+            self.http.post(&url).form(&[(namespace.as_str(), identifier)])
+        } else {
+            let url = format!(
+                "{}/{}/{}/{}/{}",
+                self.base_url,
+                prefix,
+                namespace,
+                encode_path_segment(identifier),
+                operation
+            );
+            self.http.get(&url)
+        }
+    }
+
+    /// Fetches every compound record PubChem associates with `identifier` in `namespace`.
+    ///
+    /// Formula lookups answer with a ListKey first; this waits for the records to be ready.
+    pub async fn get_compounds(&self, namespace: Namespace, identifier: &str) -> Result<Vec<Compound>> {
+        let request = self.with_record_type(self.compound_request(namespace, identifier, "JSON"));
+        let body = self.send(request).await?;
+        let body = self.resolve_waiting(body, "JSON").await?;
+        Ok(parse_compounds(&body)?)
+    }
+
+    /// Fetches only the CIDs matching `identifier`, waiting on a ListKey if PubChem hands one back.
+    pub async fn get_cids(&self, namespace: Namespace, identifier: &str) -> Result<Vec<u32>> {
+        let body = self.send(self.compound_request(namespace, identifier, "cids/JSON")).await?;
+        let body = self.resolve_waiting(body, "cids/JSON").await?;
+        parse_cids(&body)
+    }
+
+    /// Fetches only the requested `properties` for each identifier from the property table endpoint.
+    ///
+    /// CIDs and InChIKeys are sent as chunked list requests; other namespaces take one request
+    /// per identifier. Identifiers PubChem doesn't know are skipped; rows carry their CID.
+    pub async fn get_properties(
+        &self,
+        namespace: Namespace,
+        identifiers: &[String],
+        properties: &[Property],
+    ) -> Result<Vec<CompoundProperties>> {
+        if properties.is_empty() {
+            return Err(PcPugError::InvalidInput("no properties requested".to_string()));
+        }
+        let operation = format!("property/{}/JSON", property_list(properties));
+        let requests: Vec<reqwest::RequestBuilder> = if namespace.supports_list_input() {
+            identifiers
+                .chunks(DEFAULT_CHUNK_SIZE)
+                .map(|chunk| {
+                    let url = format!("{}/compound/{}/{}", self.base_url, namespace, operation);
+                    self.http.post(&url).form(&[(namespace.as_str(), chunk.join(","))])
+                })
+                .collect()
+        } else {
+            identifiers
+                .iter()
+                .map(|identifier| self.compound_request(namespace, identifier, &operation))
+                .collect()
+        };
+        let mut rows = Vec::new();
+        for request in requests {
+            let body = match self.send(request).await {
+                Ok(body) => self.resolve_waiting(body, &operation).await?,
+                Err(err) if err.is_not_found() => continue,
+                Err(err) => return Err(err),
+            };
+            let envelope: PropertyTableEnvelope = serde_json::from_str(&body)?;
+            rows.extend(envelope.table.properties);
+        }
+        Ok(rows)
+    }
+
+    /// Runs a structure search for `query` (a SMILES, InChI or CID) and returns the matching CIDs.
+    pub async fn search(
+        &self,
+        kind: SearchKind,
+        namespace: Namespace,
+        query: &str,
+        options: SearchOptions,
+    ) -> Result<Vec<u32>> {
+        if !matches!(namespace, Namespace::Smiles | Namespace::Inchi | Namespace::Cid) {
+            return Err(PcPugError::InvalidInput(format!(
+                "structure searches take smiles, inchi or cid input, not {}",
+                namespace
+            )));
+        }
+        let prefix = format!("compound/{}", kind.as_str());
+        let request = self
+            .input_request(&prefix, namespace, query, "cids/JSON")
+            .query(&kind.query_params(&options));
+        let body = self.send(request).await?;
+        let body = self.resolve_waiting(body, "cids/JSON").await?;
+        parse_cids(&body)
+    }
+
+    /// Runs a structure search and fetches the full record for every hit, in hit order.
+    pub async fn search_compounds(
+        &self,
+        kind: SearchKind,
+        namespace: Namespace,
+        query: &str,
+        options: SearchOptions,
+    ) -> Result<Vec<Compound>> {
+        let cids: Vec<String> = self
+            .search(kind, namespace, query, options)
+            .await?
+            .iter()
+            .map(u32::to_string)
+            .collect();
+        let mut compounds = Vec::new();
+        for chunk in self.get_compounds_batch(Namespace::Cid, &cids, DEFAULT_CHUNK_SIZE).await? {
+            compounds.extend(chunk.compounds?.into_iter().flatten());
+        }
+        Ok(compounds)
+    }
+
+    /// Lists CIDs for `name`, optionally matching single words within synonyms.
+    pub async fn get_cids_by_name(&self, name: &str, name_type: NameType) -> Result<Vec<u32>> {
+        let request = self
+            .compound_request(Namespace::Name, name, "cids/JSON")
+            .query(&[("name_type", name_type.as_str())]);
+        let body = self.send(request).await?;
+        let body = self.resolve_waiting(body, "cids/JSON").await?;
+        parse_cids(&body)
+    }
+
+    pub async fn get_synonyms(&self, namespace: Namespace, identifier: &str) -> Result<Vec<Synonyms>> {
+        self.get_information(namespace, identifier, "synonyms/JSON").await
+    }
+
+    /// Fetches each matching compound's title and sourced descriptions.
+    pub async fn get_description(&self, namespace: Namespace, identifier: &str) -> Result<Vec<Description>> {
+        let entries: Vec<DescriptionEntry> = self.get_information(namespace, identifier, "description/JSON").await?;
+        Ok(group_descriptions(entries))
+    }
+
+    pub async fn get_xrefs(&self, namespace: Namespace, identifier: &str, kinds: &[XrefKind]) -> Result<Vec<Xrefs>> {
+        if kinds.is_empty() {
+            return Err(PcPugError::InvalidInput("no xref types requested".to_string()));
+        }
+        let kinds: Vec<&str> = kinds.iter().map(XrefKind::as_str).collect();
+        let operation = format!("xrefs/{}/JSON", kinds.join(","));
+        let entries: Vec<XrefEntry> = self.get_information(namespace, identifier, &operation).await?;
+        Ok(entries.into_iter().map(Xrefs::from).collect())
+    }
+
+    /// Lists the conformer IDs of each matching compound's 3D conformer model.
+    pub async fn get_conformer_ids(&self, namespace: Namespace, identifier: &str) -> Result<Vec<ConformerIds>> {
+        self.get_information(namespace, identifier, "conformers/JSON").await
+    }
+
+    async fn get_information<T: serde::de::DeserializeOwned>(
+        &self,
+        namespace: Namespace,
+        identifier: &str,
+        operation: &str,
+    ) -> Result<Vec<T>> {
+        let body = self.send(self.compound_request(namespace, identifier, operation)).await?;
+        let body = self.resolve_waiting(body, operation).await?;
+        let envelope: InformationListEnvelope<T> = serde_json::from_str(&body)?;
+        Ok(envelope.list.information)
+    }
+
+    /// Fetches substance records from the `substance` domain by SID.
+    pub async fn get_substances(&self, sids: &[u32]) -> Result<Vec<Substance>> {
+        if sids.is_empty() {
+            return Err(PcPugError::InvalidInput("no SIDs requested".to_string()));
+        }
+        let url = format!("{}/substance/sid/{}/JSON", self.base_url, join_ids(sids));
+        let body = self.send(self.http.get(&url)).await?;
+        let body = self.resolve_waiting_in("substance", body, "JSON").await?;
+        let response: PcSubstances = serde_json::from_str(&body)?;
+        Ok(response.substances.into_iter().map(Substance::from).collect())
+    }
+
+    /// Fetches BioAssay overviews (targets, method, active/inactive counts) by AID.
+    pub async fn get_assay_summaries(&self, aids: &[u32]) -> Result<Vec<AssaySummary>> {
+        if aids.is_empty() {
+            return Err(PcPugError::InvalidInput("no AIDs requested".to_string()));
+        }
+        let url = format!("{}/assay/aid/{}/summary/JSON", self.base_url, join_ids(aids));
+        let body = self.send(self.http.get(&url)).await?;
+        let envelope: AssaySummariesEnvelope = serde_json::from_str(&body)?;
+        Ok(envelope.summaries.summaries)
+    }
+
+    /// Fetches every assay result recorded for the matching compounds.
+    pub async fn get_bioactivities(&self, namespace: Namespace, identifier: &str) -> Result<Vec<Bioactivity>> {
+        let body = self.send(self.compound_request(namespace, identifier, "assaysummary/JSON")).await?;
+        let body = self.resolve_waiting(body, "assaysummary/JSON").await?;
+        let envelope: TableEnvelope = serde_json::from_str(&body)?;
+        Ok(envelope.table.into_bioactivities())
+    }
+
+    /// Fetches the PUG View annotation record for `cid`, limited to one TOC heading
+    /// (e.g. `"GHS Classification"`) when `heading` is given.
+    pub async fn get_view(&self, cid: u32, heading: Option<&str>) -> Result<ViewRecord> {
+        let url = format!("{}/data/compound/{}/JSON", self.view_base_url, cid);
+        let mut request = self.http.get(&url);
+        if let Some(heading) = heading {
+            request = request.query(&[("heading", heading)]);
+        }
+        let body = self.send(request).await?;
+        let envelope: ViewEnvelope = serde_json::from_str(&body)?;
+        Ok(envelope.record)
+    }
+
+    /// GHS pictograms, signal word and H/P statements for `cid`; empty when PubChem has none.
+    pub async fn get_ghs_classification(&self, cid: u32) -> Result<GhsClassification> {
+        match self.get_view(cid, Some(GHS_HEADING)).await {
+            Ok(record) => Ok(GhsClassification::from_record(&record)),
+            Err(err) if err.is_not_found() => Ok(GhsClassification::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Boiling point, solubility and other measured properties for `cid`; empty when PubChem has none.
+    pub async fn get_experimental_properties(&self, cid: u32) -> Result<ExperimentalProperties> {
+        match self.get_view(cid, Some(EXPERIMENTAL_HEADING)).await {
+            Ok(record) => Ok(ExperimentalProperties::from_record(&record)),
+            Err(err) if err.is_not_found() => Ok(ExperimentalProperties::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Waits for a ListKey returned by an earlier request and returns its CIDs.
+    pub async fn get_listkey_cids(&self, list_key: &str) -> Result<Vec<u32>> {
+        let body = self.poll_listkey("compound", list_key, "cids/JSON").await?;
+        parse_cids(&body)
+    }
+
+    /// Passes `body` through unless it is a `Waiting` response, in which case the
+    /// ListKey is polled for `operation` until the real result arrives.
+    async fn resolve_waiting(&self, body: String, operation: &str) -> Result<String> {
+        self.resolve_waiting_in("compound", body, operation).await
+    }
+
+    /// [`resolve_waiting`](Self::resolve_waiting) for a ListKey issued by another domain.
+    async fn resolve_waiting_in(&self, domain: &str, body: String, operation: &str) -> Result<String> {
+        match Waiting::from_body(&body) {
+            Some(waiting) => self.poll_listkey(domain, &waiting.list_key, operation).await,
+            None => Ok(body),
+        }
+    }
+
+    async fn poll_listkey(&self, domain: &str, list_key: &str, operation: &str) -> Result<String> {
+        let policy = self.poll_policy;
+        let started = Instant::now();
+        let url = format!(
+            "{}/{}/listkey/{}/{}",
+            self.base_url,
+            domain,
+            encode_path_segment(list_key),
+            operation
+        );
+        let mut interval = policy.initial_interval;
+        loop {
+            tokio::time::sleep(interval).await;
+            let mut request = self.http.get(&url);
+            if domain == "compound" && operation == "JSON" {
+                request = self.with_record_type(request);
+            }
+            let body = self.send(request).await?;
+            if Waiting::from_body(&body).is_none() {
+                return Ok(body);
+            }
+            if started.elapsed() >= policy.deadline {
+                return Err(PcPugError::Timeout(Fault::new(
+                    "PUGREST.Timeout",
+                    format!("ListKey {} still running after {:?}", list_key, policy.deadline),
+                )));
+            }
+            interval = (interval * 2).min(policy.max_interval);
+        }
+    }
+
+    /// Looks up many identifiers with at most `jobs` requests in flight.
+    ///
+    /// Results are yielded in input order, paired with their identifier. Requests still go
+    /// through the shared rate limiter, so `jobs` bounds parallelism, not throughput.
+    pub fn fetch_many<'a>(
+        &'a self,
+        namespace: Namespace,
+        identifiers: impl IntoIterator<Item = String> + 'a,
+        jobs: usize,
+    ) -> impl Stream<Item = (String, Result<Vec<Compound>>)> + 'a {
+        stream::iter(identifiers)
+            .map(move |identifier| async move {
+                let result = self.get_compounds(namespace, &identifier).await;
+                (identifier, result)
+            })
+            .buffered(jobs.max(1))
+    }
+
+    /// Fetches CIDs or InChIKeys in chunks of `chunk_size` per request.
+    ///
+    /// Each chunk is POSTed as a comma-separated list and the multi-record response is
+    /// matched back to its inputs. Identifiers PubChem returned nothing for map to `None`.
+    /// A chunk that still fails after retries is reported in its own [`BatchChunk`] and the
+    /// remaining chunks are fetched regardless.
+    pub async fn get_compounds_batch(
+        &self,
+        namespace: Namespace,
+        identifiers: &[String],
+        chunk_size: usize,
+    ) -> Result<Vec<BatchChunk>> {
+        if !namespace.supports_list_input() {
+            return Err(PcPugError::InvalidInput(format!(
+                "list input is only supported for cid and inchikey, not {}",
+                namespace
+            )));
+        }
+        let mut chunks = Vec::new();
+        for chunk in identifiers.chunks(chunk_size.max(1)) {
+            let compounds = self.get_compounds_chunk(namespace, chunk).await;
+            chunks.push(BatchChunk {
+                identifiers: chunk.to_vec(),
+                compounds,
+            });
+        }
+        Ok(chunks)
+    }
+
+    async fn get_compounds_chunk(&self, namespace: Namespace, chunk: &[String]) -> Result<Vec<Option<Compound>>> {
+        let keys: Vec<String> = chunk
+            .iter()
+            .filter_map(|identifier| list_key(namespace, identifier))
+            .collect();
+        if keys.is_empty() {
+            return Ok(chunk.iter().map(|_| None).collect());
+        }
+        let url = format!("{}/compound/{}/JSON", self.base_url, namespace);
+        let request = self.with_record_type(self.http.post(&url)).form(&[(namespace.as_str(), keys.join(","))]);
+        let compounds = match self.send(request).await {
+            Ok(body) => parse_compounds(&self.resolve_waiting(body, "JSON").await?)?,
+            // PubChem answers 404 only when nothing in the list matched.
+            Err(err) if err.is_not_found() => Vec::new(),
+            Err(err) => return Err(err),
+        };
+        let mut found: HashMap<String, Compound> = HashMap::new();
+        for compound in compounds {
+            let key = match namespace {
+                Namespace::Cid => compound.cid.map(|cid| cid.to_string()),
+                _ => compound.inchikey.clone(),
+            };
+            if let Some(key) = key {
+                found.entry(key).or_insert(compound);
+            }
+        }
+        Ok(chunk
+            .iter()
+            .map(|identifier| list_key(namespace, identifier).and_then(|key| found.remove(&key)))
+            .collect())
+    }
+
+    /// Fetches raw server output for `identifier` in `format`, e.g. an SDF or a server-rendered PNG.
+    ///
+    /// `operation` selects what to render (`cids`, `synonyms`, `property/MolecularWeight,XLogP`, ...);
+    /// `None` downloads the full record, honouring the client's [`RecordType`]. A ListKey answer
+    /// is waited on and the output then fetched from the finished list.
+    pub async fn download(
+        &self,
+        namespace: Namespace,
+        identifier: &str,
+        operation: Option<&str>,
+        format: OutputFormat,
+    ) -> Result<Vec<u8>> {
+        let body = self.send_bytes(self.download_request(namespace, identifier, operation, format)?).await?;
+        let waiting = std::str::from_utf8(&body).ok().and_then(Waiting::from_body);
+        let Some(waiting) = waiting else {
+            return Ok(body);
+        };
+        self.poll_listkey("compound", &waiting.list_key, "cids/JSON").await?;
+        let request = self.download_request(Namespace::ListKey, &waiting.list_key, operation, format)?;
+        self.send_bytes(request).await
+    }
+
+    fn download_request(
+        &self,
+        namespace: Namespace,
+        identifier: &str,
+        operation: Option<&str>,
+        format: OutputFormat,
+    ) -> Result<reqwest::RequestBuilder> {
+        match operation {
+            Some(operation) => {
+                let operation = format!("{}/{}", operation.trim_matches('/'), format);
+                Ok(self.compound_request(namespace, identifier, &operation))
+            }
+            None if format.supports_full_records() => {
+                Ok(self.with_record_type(self.compound_request(namespace, identifier, format.as_str())))
+            }
+            None => Err(PcPugError::InvalidInput(format!(
+                "{} output needs an operation such as cids or property/...",
+                format
+            ))),
+        }
+    }
+
+    pub async fn get_compounds_by_name(&self, name: &str) -> Result<Vec<Compound>> {
+        self.get_compounds(Namespace::Name, name).await
+    }
+
+    pub async fn get_compound_by_cid(&self, cid: u32) -> Result<Vec<Compound>> {
+        self.get_compounds(Namespace::Cid, &cid.to_string()).await
+    }
+
+    /// Sends a request and returns the body as text; see [`PubChemClient::send_bytes`].
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<String> {
+        let body = self.send_bytes(request).await?;
+        Ok(String::from_utf8_lossy(&body).into_owned())
+    }
+
+    /// Sends a request and returns the raw body, answering from the response cache when one is set.
+    async fn send_bytes(&self, request: reqwest::RequestBuilder) -> Result<Vec<u8>> {
+        let Some(cache) = &self.cache else {
+            return self.send_with_retries(request).await;
+        };
+        let Some((key, description)) = cache_key(&request) else {
+            return self.send_with_retries(request).await;
+        };
+        if let Some(body) = cache.get(&key) {
+            return Ok(body);
+        }
+        if cache.is_offline() {
+            return Err(PcPugError::NotCached(description));
+        }
+        let body = self.send_with_retries(request).await?;
+        let pending = std::str::from_utf8(&body).ok().and_then(Waiting::from_body).is_some();
+        if !pending {
+            // A cache that can't be written only costs a refetch next time.
+            let _ = cache.put(&key, &description, &body);
+        }
+        Ok(body)
+    }
+
+    /// Sends a request, turning PubChem faults into [`PcPugError`]s and retrying
+    /// retryable ones according to the client's [`RetryPolicy`].
+    async fn send_with_retries(&self, request: reqwest::RequestBuilder) -> Result<Vec<u8>> {
+        let policy = self.retry_policy;
+        let request = match policy.timeout {
+            Some(timeout) => request.timeout(timeout),
+            None => request,
+        };
+        let mut attempt = 1;
+        loop {
+            // Bodies here are always in-memory forms, so cloning only fails for streams.
+            let Some(this_attempt) = request.try_clone() else {
+                return self.send_once(request).await;
+            };
+            match self.send_once(this_attempt).await {
+                Err(err) if err.is_retryable() && attempt < policy.max_attempts => {
+                    tokio::time::sleep(policy.backoff(attempt)).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn send_once(&self, request: reqwest::RequestBuilder) -> Result<Vec<u8>> {
+        self.rate_limiter.acquire().await;
+        let response = request.send().await?;
+        if let Some(header) = response.headers().get(THROTTLING_HEADER).and_then(|v| v.to_str().ok()) {
+            self.rate_limiter.observe(header);
+        }
+        let status = response.status();
+        let body = response.bytes().await?;
+        if status.is_success() {
+            Ok(body.to_vec())
+        } else {
+            Err(PcPugError::from_response(status.as_u16(), &String::from_utf8_lossy(&body)))
+        }
+    }
+}
+
+/// Normalises an identifier for list requests; `None` for CIDs that aren't numbers.
+fn list_key(namespace: Namespace, identifier: &str) -> Option<String> {
+    let identifier = identifier.trim();
+    match namespace {
+        Namespace::Cid => identifier.parse::<u32>().ok().map(|cid| cid.to_string()),
+        _ => Some(identifier.to_ascii_uppercase()),
+    }
+}
+
+/// Cache key and `METHOD url` description of a request; `None` if it can't be built or cloned.
+fn cache_key(request: &reqwest::RequestBuilder) -> Option<(String, String)> {
+    let request = request.try_clone()?.build().ok()?;
+    let body = match request.body() {
+        Some(body) => body.as_bytes()?,
+        None => &[],
+    };
+    let key = ResponseCache::key(request.method().as_str(), request.url().as_str(), body);
+    Some((key, format!("{} {}", request.method(), request.url())))
+}
+
+fn join_ids(ids: &[u32]) -> String {
+    ids.iter().map(u32::to_string).collect::<Vec<_>>().join(",")
+}
+
+fn parse_cids(body: &str) -> Result<Vec<u32>> {
+    let envelope: IdentifierListEnvelope = serde_json::from_str(body)?;
+    Ok(envelope.identifier_list.cids)
+}
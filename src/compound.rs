@@ -0,0 +1,1076 @@
+//! Data model for PUG REST compound records.
+
+use crate::element::Element;
+use serde::de::{DeserializeOwned, Error as _};
+use serde::{Deserialize, Deserializer};
+use std::collections::HashMap;
+use std::fmt;
+
+#[derive(Debug, Deserialize)]
+pub struct AtomInfo {
+    pub aid: Vec<u32>,
+    pub element: Vec<u32>,
+    /// Formal charges; only atoms with a non-zero charge are listed.
+    #[serde(default)]
+    pub charge: Vec<AtomInt>,
+    /// Mass numbers; only atoms that aren't the natural isotope mix are listed.
+    #[serde(default)]
+    pub isotope: Vec<AtomInt>,
+    /// Unpaired-electron states; only radical atoms are listed.
+    #[serde(default)]
+    pub radical: Vec<AtomRadical>,
+}
+
+/// A per-atom integer annotation (`PC-AtomInt`), keyed by atom id.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct AtomInt {
+    pub aid: u32,
+    pub value: i32,
+}
+
+/// An atom's radical state (`PC-AtomRadical`); `kind` is the raw PubChem code.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct AtomRadical {
+    pub aid: u32,
+    #[serde(rename = "type")]
+    pub kind: u32,
+}
+
+/// Spin multiplicity of a radical atom.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Radical {
+    Singlet,
+    Doublet,
+    Triplet,
+    Quartet,
+    Quintet,
+    Hextet,
+    Heptet,
+    Octet,
+}
+
+impl Radical {
+    /// Decodes PubChem's radical type; 255 ("none") and unknown codes give `None`.
+    pub fn from_code(code: u32) -> Option<Self> {
+        match code {
+            1 => Some(Radical::Singlet),
+            2 => Some(Radical::Doublet),
+            3 => Some(Radical::Triplet),
+            4 => Some(Radical::Quartet),
+            5 => Some(Radical::Quintet),
+            6 => Some(Radical::Hextet),
+            7 => Some(Radical::Heptet),
+            8 => Some(Radical::Octet),
+            _ => None,
+        }
+    }
+
+    /// Non-bonding electrons the state takes away from the atom's valence: two for a singlet
+    /// (a carbene's lone pair), otherwise one per unpaired electron.
+    pub fn electrons(self) -> u32 {
+        match self {
+            Radical::Singlet => 2,
+            Radical::Doublet => 1,
+            Radical::Triplet => 2,
+            Radical::Quartet => 3,
+            Radical::Quintet => 4,
+            Radical::Hextet => 5,
+            Radical::Heptet => 6,
+            Radical::Octet => 7,
+        }
+    }
+}
+
+impl fmt::Display for Radical {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Radical::Singlet => "singlet",
+            Radical::Doublet => "doublet",
+            Radical::Triplet => "triplet",
+            Radical::Quartet => "quartet",
+            Radical::Quintet => "quintet",
+            Radical::Hextet => "hextet",
+            Radical::Heptet => "heptet",
+            Radical::Octet => "octet",
+        };
+        f.write_str(name)
+    }
+}
+
+impl AtomInfo {
+    /// Maps each PubChem atom id to its position in the parallel atom arrays.
+    pub fn aid_index(&self) -> HashMap<u32, usize> {
+        self.aid.iter().enumerate().map(|(idx, &aid)| (aid, idx)).collect()
+    }
+
+    /// Formal charge of atom `aid`, 0 when unlisted.
+    pub fn charge_of(&self, aid: u32) -> i32 {
+        self.charge.iter().find(|c| c.aid == aid).map_or(0, |c| c.value)
+    }
+
+    /// Mass number of atom `aid` if it is a specific isotope.
+    pub fn isotope_of(&self, aid: u32) -> Option<u32> {
+        self.isotope
+            .iter()
+            .find(|i| i.aid == aid)
+            .and_then(|i| u32::try_from(i.value).ok())
+    }
+
+    /// Radical state of atom `aid`, if it has one.
+    pub fn radical_of(&self, aid: u32) -> Option<Radical> {
+        self.radical
+            .iter()
+            .find(|r| r.aid == aid)
+            .and_then(|r| Radical::from_code(r.kind))
+    }
+
+    /// Resolves each atom's atomic number; `None` for PubChem pseudo-elements such as R groups.
+    pub fn elements(&self) -> impl Iterator<Item = Option<&'static Element>> + '_ {
+        self.element.iter().map(|&number| Element::from_number(number))
+    }
+}
+
+/// PubChem's bond table: one entry per bond spread across three parallel arrays.
+#[derive(Debug, Deserialize)]
+pub struct BondInfo {
+    pub aid1: Vec<u32>,
+    pub aid2: Vec<u32>,
+    pub order: Vec<u32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bond {
+    pub aid1: u32,
+    pub aid2: u32,
+    pub order: u32,
+}
+
+impl BondInfo {
+    pub fn iter(&self) -> impl Iterator<Item = Bond> + '_ {
+        self.aid1
+            .iter()
+            .zip(&self.aid2)
+            .zip(&self.order)
+            .map(|((&aid1, &aid2), &order)| Bond { aid1, aid2, order })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Conformer {
+    pub x: Vec<f64>,
+    pub y: Vec<f64>,
+    /// Only present in 3D records.
+    pub z: Option<Vec<f64>>,
+    /// Per-conformer properties of 3D records (ID, energy, shape volume, RMSD, ...).
+    #[serde(default)]
+    pub data: Vec<Props>,
+}
+
+impl Conformer {
+    pub fn is_3d(&self) -> bool {
+        self.z.is_some()
+    }
+
+    /// Looks up a per-conformer property by its URN label and name.
+    pub fn datum(&self, label: &str, name: &str) -> Option<&PropertyValue> {
+        self.data
+            .iter()
+            .find(|datum| datum.urn.label.as_deref() == Some(label) && datum.urn.name.as_deref() == Some(name))
+            .map(|datum| &datum.value)
+    }
+
+    /// PubChem conformer ID, e.g. `000008C400000001`.
+    pub fn id(&self) -> Option<&str> {
+        self.datum("Conformer", "ID").and_then(PropertyValue::as_str)
+    }
+
+    /// MMFF94 energy (kcal/mol, electrostatics excluded).
+    pub fn energy(&self) -> Option<f64> {
+        self.datum("Energy", "MMFF94 NoEstat").and_then(PropertyValue::as_f64)
+    }
+
+    /// Shape volume in cubic angstroms.
+    pub fn volume(&self) -> Option<f64> {
+        self.datum("Shape", "Volume").and_then(PropertyValue::as_f64)
+    }
+
+    /// RMSD threshold used when sampling the conformer model, in angstroms.
+    pub fn rmsd(&self) -> Option<f64> {
+        self.datum("Conformer", "RMSD").and_then(PropertyValue::as_f64)
+    }
+}
+
+/// Which coordinate set to request for full records.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RecordType {
+    #[default]
+    TwoD,
+    ThreeD,
+}
+
+impl RecordType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RecordType::TwoD => "2d",
+            RecordType::ThreeD => "3d",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Coords {
+    pub conformers: Vec<Conformer>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Props {
+    pub urn: Urn,
+    pub value: PropertyValue,
+}
+
+/// One stereo descriptor (`PC-StereoCenter`), keyed in the JSON by its geometry.
+///
+/// Atom fields name positions around the center as PubChem's 2D depiction places them; parity
+/// and type fields keep PubChem's codes, decoded by [`Stereo::parity`] and [`Stereo::name`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Stereo {
+    Tetrahedral(Tetrahedral),
+    /// Double-bond (or cumulene) cis/trans arrangement.
+    Planar(Planar),
+    SquarePlanar(SquarePlanar),
+    Octahedral(Octahedral),
+    TrigonalBipyramid(TrigonalBipyramid),
+    TShape(TShape),
+    PentagonalBipyramid(PentagonalBipyramid),
+    /// A geometry this crate doesn't know yet, by its JSON key; its fields are dropped so one new
+    /// descriptor doesn't fail the whole record.
+    Unknown(String),
+}
+
+impl<'de> Deserialize<'de> for Stereo {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        fn fields<T: DeserializeOwned, E: serde::de::Error>(value: serde_json::Value) -> Result<T, E> {
+            serde_json::from_value(value).map_err(E::custom)
+        }
+        let entry = serde_json::Map::<String, serde_json::Value>::deserialize(deserializer)?;
+        let mut entries = entry.into_iter();
+        let (Some((geometry, value)), None) = (entries.next(), entries.next()) else {
+            return Err(D::Error::custom("expected exactly one stereo geometry per descriptor"));
+        };
+        Ok(match geometry.as_str() {
+            "tetrahedral" => Stereo::Tetrahedral(fields(value)?),
+            "planar" => Stereo::Planar(fields(value)?),
+            "squareplanar" => Stereo::SquarePlanar(fields(value)?),
+            "octahedral" => Stereo::Octahedral(fields(value)?),
+            "bipyramid" => Stereo::TrigonalBipyramid(fields(value)?),
+            "tshape" => Stereo::TShape(fields(value)?),
+            "pentagonal" => Stereo::PentagonalBipyramid(fields(value)?),
+            _ => Stereo::Unknown(geometry),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Tetrahedral {
+    pub above: u32,
+    pub below: u32,
+    pub bottom: u32,
+    pub center: u32,
+    pub parity: u32,
+    pub top: u32,
+    /// 1 tetrahedral, 2 cumulenic, 3 biaryl.
+    #[serde(rename = "type")]
+    pub ttype: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Planar {
+    pub left: u32,
+    pub ltop: u32,
+    pub lbottom: u32,
+    pub right: u32,
+    pub rtop: u32,
+    pub rbottom: u32,
+    pub parity: u32,
+    /// 1 planar, 2 cumulenic.
+    #[serde(rename = "type")]
+    pub kind: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct SquarePlanar {
+    pub center: u32,
+    pub lbelow: u32,
+    pub rbelow: u32,
+    pub labove: u32,
+    pub rabove: u32,
+    pub parity: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Octahedral {
+    pub center: u32,
+    pub top: u32,
+    pub bottom: u32,
+    pub labove: u32,
+    pub lbelow: u32,
+    pub rabove: u32,
+    pub rbelow: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct TrigonalBipyramid {
+    pub center: u32,
+    pub above: u32,
+    pub below: u32,
+    pub top: u32,
+    pub bottom: u32,
+    pub right: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct TShape {
+    pub center: u32,
+    pub top: u32,
+    pub bottom: u32,
+    pub above: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct PentagonalBipyramid {
+    pub center: u32,
+    pub top: u32,
+    pub bottom: u32,
+    pub left: u32,
+    pub labove: u32,
+    pub lbelow: u32,
+    pub rabove: u32,
+    pub rbelow: u32,
+}
+
+/// Decoded stereo parity. Which values occur depends on the geometry: clockwise/counterclockwise
+/// for tetrahedral centers, same/opposite side for planar ones, U/Z/X shapes for square-planar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Parity {
+    Clockwise,
+    Counterclockwise,
+    /// The top substituents of a planar descriptor sit on the same side of the double bond.
+    Same,
+    Opposite,
+    UShape,
+    ZShape,
+    XShape,
+    /// Deliberately unspecified: either configuration.
+    Any,
+    Unknown,
+}
+
+impl fmt::Display for Parity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Parity::Clockwise => "clockwise",
+            Parity::Counterclockwise => "counterclockwise",
+            Parity::Same => "same side",
+            Parity::Opposite => "opposite sides",
+            Parity::UShape => "U-shape",
+            Parity::ZShape => "Z-shape",
+            Parity::XShape => "X-shape",
+            Parity::Any => "any",
+            Parity::Unknown => "unknown",
+        };
+        f.write_str(name)
+    }
+}
+
+impl Stereo {
+    /// Geometry name, including the tetrahedral and planar sub-types.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Stereo::Tetrahedral(t) if t.ttype == 2 => "cumulenic tetrahedral",
+            Stereo::Tetrahedral(t) if t.ttype == 3 => "biaryl",
+            Stereo::Tetrahedral(_) => "tetrahedral",
+            Stereo::Planar(p) if p.kind == 2 => "cumulenic planar",
+            Stereo::Planar(_) => "planar",
+            Stereo::SquarePlanar(_) => "square planar",
+            Stereo::Octahedral(_) => "octahedral",
+            Stereo::TrigonalBipyramid(_) => "trigonal bipyramidal",
+            Stereo::TShape(_) => "T-shaped",
+            Stereo::PentagonalBipyramid(_) => "pentagonal bipyramidal",
+            Stereo::Unknown(_) => "unknown geometry",
+        }
+    }
+
+    /// The stereocenter's atom id; planar descriptors have a bond (`left`/`right`) instead.
+    pub fn center(&self) -> Option<u32> {
+        match self {
+            Stereo::Tetrahedral(t) => Some(t.center),
+            Stereo::Planar(_) | Stereo::Unknown(_) => None,
+            Stereo::SquarePlanar(s) => Some(s.center),
+            Stereo::Octahedral(o) => Some(o.center),
+            Stereo::TrigonalBipyramid(b) => Some(b.center),
+            Stereo::TShape(t) => Some(t.center),
+            Stereo::PentagonalBipyramid(p) => Some(p.center),
+        }
+    }
+
+    /// Every atom the descriptor names, center (or `left`, `right`) first.
+    pub fn atoms(&self) -> Vec<u32> {
+        match self {
+            Stereo::Tetrahedral(t) => vec![t.center, t.above, t.top, t.bottom, t.below],
+            Stereo::Planar(p) => vec![p.left, p.right, p.ltop, p.lbottom, p.rtop, p.rbottom],
+            Stereo::SquarePlanar(s) => vec![s.center, s.lbelow, s.rbelow, s.labove, s.rabove],
+            Stereo::Octahedral(o) => vec![o.center, o.top, o.bottom, o.labove, o.lbelow, o.rabove, o.rbelow],
+            Stereo::TrigonalBipyramid(b) => vec![b.center, b.above, b.below, b.top, b.bottom, b.right],
+            Stereo::TShape(t) => vec![t.center, t.top, t.bottom, t.above],
+            Stereo::PentagonalBipyramid(p) => {
+                vec![p.center, p.top, p.bottom, p.left, p.labove, p.lbelow, p.rabove, p.rbelow]
+            }
+            Stereo::Unknown(_) => Vec::new(),
+        }
+    }
+
+    /// Decoded parity; geometries without a parity code give `None`.
+    pub fn parity(&self) -> Option<Parity> {
+        let parity = match self {
+            Stereo::Tetrahedral(t) => match t.parity {
+                1 => Parity::Clockwise,
+                2 => Parity::Counterclockwise,
+                3 => Parity::Any,
+                _ => Parity::Unknown,
+            },
+            Stereo::Planar(p) => match p.parity {
+                1 => Parity::Same,
+                2 => Parity::Opposite,
+                3 => Parity::Any,
+                _ => Parity::Unknown,
+            },
+            Stereo::SquarePlanar(s) => match s.parity {
+                1 => Parity::UShape,
+                2 => Parity::ZShape,
+                3 => Parity::XShape,
+                4 => Parity::Any,
+                _ => Parity::Unknown,
+            },
+            _ => return None,
+        };
+        Some(parity)
+    }
+}
+
+/// One line per descriptor, e.g. `tetrahedral at atom 4 (above 7, top 3, bottom 5, below 6): clockwise`.
+impl fmt::Display for Stereo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let positions: Vec<(&str, u32)> = match self {
+            Stereo::Unknown(geometry) => return write!(f, "{} `{}`", self.name(), geometry),
+            Stereo::Tetrahedral(t) => vec![
+                ("above", t.above),
+                ("top", t.top),
+                ("bottom", t.bottom),
+                ("below", t.below),
+            ],
+            Stereo::Planar(p) => vec![
+                ("left top", p.ltop),
+                ("left bottom", p.lbottom),
+                ("right top", p.rtop),
+                ("right bottom", p.rbottom),
+            ],
+            Stereo::SquarePlanar(s) => vec![
+                ("left below", s.lbelow),
+                ("right below", s.rbelow),
+                ("left above", s.labove),
+                ("right above", s.rabove),
+            ],
+            Stereo::Octahedral(o) => vec![
+                ("top", o.top),
+                ("bottom", o.bottom),
+                ("left above", o.labove),
+                ("left below", o.lbelow),
+                ("right above", o.rabove),
+                ("right below", o.rbelow),
+            ],
+            Stereo::TrigonalBipyramid(b) => vec![
+                ("above", b.above),
+                ("below", b.below),
+                ("top", b.top),
+                ("bottom", b.bottom),
+                ("right", b.right),
+            ],
+            Stereo::TShape(t) => vec![("top", t.top), ("bottom", t.bottom), ("above", t.above)],
+            Stereo::PentagonalBipyramid(p) => vec![
+                ("top", p.top),
+                ("bottom", p.bottom),
+                ("left", p.left),
+                ("left above", p.labove),
+                ("left below", p.lbelow),
+                ("right above", p.rabove),
+                ("right below", p.rbelow),
+            ],
+        };
+        match self {
+            Stereo::Planar(p) => write!(f, "{} bond {}={}", self.name(), p.left, p.right)?,
+            _ => write!(f, "{} at atom {}", self.name(), self.center().unwrap_or_default())?,
+        }
+        let positions: Vec<String> = positions.iter().map(|(name, aid)| format!("{} {}", name, aid)).collect();
+        write!(f, " ({})", positions.join(", "))?;
+        if let Some(parity) = self.parity() {
+            write!(f, ": {}", parity)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Urn {
+    pub datatype: Option<u32>,
+    pub label: Option<String>,
+    pub name: Option<String>,
+    pub release: Option<String>,
+    pub software: Option<String>,
+    pub source: Option<String>,
+    pub version: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PropertyValue {
+    pub fval: Option<f64>,
+    pub ival: Option<u32>,
+    pub sval: Option<String>,
+    pub binary: Option<String>,
+}
+
+impl PropertyValue {
+    /// Numeric view of the value; PubChem sends masses and weights as strings.
+    pub fn as_f64(&self) -> Option<f64> {
+        self.fval
+            .or_else(|| self.ival.map(f64::from))
+            .or_else(|| self.sval.as_deref().and_then(|s| s.trim().parse().ok()))
+    }
+
+    pub fn as_u32(&self) -> Option<u32> {
+        self.ival
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        self.sval.as_deref()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct IUPACName {
+    #[serde(rename = "IUPACName")]
+    pub name: Option<String>,
+    /// Naming style from the props URN (e.g. "Preferred", "Traditional").
+    #[serde(skip)]
+    pub style: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PcCompounds {
+    #[serde(rename = "PC_Compounds")]
+    pub compounds: Vec<PcCompound>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PcCompound {
+    pub id: PcCompoundId,
+    pub atoms: Option<AtomInfo>,
+    pub bonds: Option<BondInfo>,
+    pub coords: Option<Vec<Coords>>,
+    pub charge: Option<i32>,
+    pub props: Option<Vec<Props>>,
+    pub stereo: Option<Vec<Stereo>>,
+    pub count: Option<Count>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PcCompoundId {
+    pub id: PcCid,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PcCid {
+    pub cid: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Count {
+    pub heavy_atom: u32,
+    pub atom_chiral: u32,
+    pub atom_chiral_def: u32,
+    pub atom_chiral_undef: u32,
+    pub bond_chiral: u32,
+    pub bond_chiral_def: u32,
+    pub bond_chiral_undef: u32,
+    pub isotope_atom: u32,
+    pub covalent_unit: u32,
+    pub tautomers: i32,
+}
+
+#[derive(Debug)]
+pub struct Compound {
+    pub cid: Option<u32>,
+    pub atoms: Option<AtomInfo>,
+    pub bonds: Option<BondInfo>,
+    pub coords: Option<Vec<Coords>>,
+    pub props: Option<Vec<Props>>,
+    pub stereo: Option<Vec<Stereo>>,
+    pub molecular_formula: Option<String>,
+    pub molecular_weight: Option<f64>,
+    pub inchi: Option<String>,
+    pub inchikey: Option<String>,
+    pub isomeric_smiles: Option<String>,
+    pub tpsa: Option<f64>,
+    pub xlogp: Option<f64>,
+    /// Mass of the most likely isotopic composition, which for Br2 or Cl3 and up is not the monoisotopic one.
+    pub exact_mass: Option<f64>,
+    pub monoisotopic_mass: Option<f64>,
+    pub complexity: Option<f64>,
+    pub h_bond_donor_count: Option<u32>,
+    pub h_bond_acceptor_count: Option<u32>,
+    pub rotatable_bond_count: Option<u32>,
+    pub heavy_atom_count: Option<u32>,
+    pub charge: Option<i32>,
+    pub iupac: Option<Vec<IUPACName>>,
+}
+
+impl From<PcCompound> for Compound {
+    fn from(record: PcCompound) -> Self {
+        let mut compound = Compound {
+            cid: Some(record.id.id.cid),
+            atoms: record.atoms,
+            bonds: record.bonds,
+            coords: record.coords,
+            props: record.props,
+            stereo: record.stereo,
+            molecular_formula: None,
+            molecular_weight: None,
+            inchi: None,
+            inchikey: None,
+            isomeric_smiles: None,
+            tpsa: None,
+            xlogp: None,
+            exact_mass: None,
+            monoisotopic_mass: None,
+            complexity: None,
+            h_bond_donor_count: None,
+            h_bond_acceptor_count: None,
+            rotatable_bond_count: None,
+            heavy_atom_count: record.count.map(|count| count.heavy_atom),
+            charge: record.charge,
+            iupac: None,
+        };
+        compound.resolve_props();
+        compound
+    }
+}
+
+impl Compound {
+    /// Looks up a raw computed property by its URN label and name.
+    pub fn prop(&self, label: &str, name: Option<&str>) -> Option<&PropertyValue> {
+        self.props
+            .as_ref()?
+            .iter()
+            .find(|prop| prop.urn.label.as_deref() == Some(label) && prop.urn.name.as_deref() == name)
+            .map(|prop| &prop.value)
+    }
+
+    /// Fills the typed descriptor fields from the `props` URN list.
+    fn resolve_props(&mut self) {
+        let Some(props) = &self.props else {
+            return;
+        };
+        let mut iupac = Vec::new();
+        for prop in props {
+            let value = &prop.value;
+            match (prop.urn.label.as_deref(), prop.urn.name.as_deref()) {
+                (Some("Molecular Formula"), None) => self.molecular_formula = value.as_str().map(String::from),
+                (Some("Molecular Weight"), None) => self.molecular_weight = value.as_f64(),
+                (Some("InChI"), Some("Standard")) => self.inchi = value.as_str().map(String::from),
+                (Some("InChIKey"), Some("Standard")) => self.inchikey = value.as_str().map(String::from),
+                // Older records label the stereo-aware SMILES "Isomeric", newer ones "Absolute".
+                (Some("SMILES"), Some("Isomeric" | "Absolute")) => self.isomeric_smiles = value.as_str().map(String::from),
+                (Some("Topological"), Some("Polar Surface Area")) => self.tpsa = value.as_f64(),
+                (Some("Log P"), Some("XLogP3" | "XLogP3-AA")) => self.xlogp = value.as_f64(),
+                (Some("Mass"), Some("Exact")) => self.exact_mass = value.as_f64(),
+                (Some("Weight"), Some("MonoIsotopic")) => self.monoisotopic_mass = value.as_f64(),
+                (Some("Compound Complexity"), None) => self.complexity = value.as_f64(),
+                (Some("Count"), Some("Hydrogen Bond Donor")) => self.h_bond_donor_count = value.as_u32(),
+                (Some("Count"), Some("Hydrogen Bond Acceptor")) => self.h_bond_acceptor_count = value.as_u32(),
+                (Some("Count"), Some("Rotatable Bond")) => self.rotatable_bond_count = value.as_u32(),
+                (Some("IUPAC Name"), style) if style != Some("Markup") => iupac.push(IUPACName {
+                    name: value.as_str().map(String::from),
+                    style: style.map(String::from),
+                }),
+                _ => {}
+            }
+        }
+        // Keep the preferred name first so callers can take `iupac[0]`.
+        iupac.sort_by_key(|name| name.style.as_deref() != Some("Preferred"));
+        if !iupac.is_empty() {
+            self.iupac = Some(iupac);
+        }
+    }
+}
+
+/// Decodes a PUG REST `PC_Compounds` JSON body into its compound records.
+pub fn parse_compounds(body: &str) -> Result<Vec<Compound>, serde_json::Error> {
+    let response: PcCompounds = serde_json::from_str(body)?;
+    Ok(response.compounds.into_iter().map(Compound::from).collect())
+}
+
+impl fmt::Display for Compound {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Compound Information:")?;
+        writeln!(f, "---------------------")?;
+        writeln!(f, "CID: {:?}", self.cid)?;
+        writeln!(f, "Molecular Formula: {:?}", self.molecular_formula)?;
+        writeln!(f, "Molecular Weight: {:?}", self.molecular_weight)?;
+        writeln!(f, "InChI: {:?}", self.inchi)?;
+        writeln!(f, "InChIKey: {:?}", self.inchikey)?;
+        writeln!(f, "Isomeric SMILES: {:?}", self.isomeric_smiles)?;
+        writeln!(f, "TPSA: {:?}", self.tpsa)?;
+        writeln!(f, "XLogP: {:?}", self.xlogp)?;
+        writeln!(f, "Exact Mass: {:?}", self.exact_mass)?;
+        writeln!(f, "Monoisotopic Mass: {:?}", self.monoisotopic_mass)?;
+        writeln!(f, "Complexity: {:?}", self.complexity)?;
+        writeln!(f, "H-Bond Donor Count: {:?}", self.h_bond_donor_count)?;
+        writeln!(f, "H-Bond Acceptor Count: {:?}", self.h_bond_acceptor_count)?;
+        writeln!(f, "Rotatable Bond Count: {:?}", self.rotatable_bond_count)?;
+        writeln!(f, "Heavy Atom Count: {:?}", self.heavy_atom_count)?;
+        writeln!(f, "Charge: {:?}", self.charge)?;
+        writeln!(f, "---------------------")?;
+        if let Some(atoms) = &self.atoms {
+            writeln!(f, "Atoms:")?;
+            for (i, (number, element)) in atoms.element.iter().zip(atoms.elements()).enumerate() {
+                let aid = atoms.aid[i];
+                match element {
+                    Some(element) => write!(f, "  Atom ID: {}, Element: {}", aid, element)?,
+                    None => write!(f, "  Atom ID: {}, Element: {}", aid, number)?,
+                }
+                let charge = atoms.charge_of(aid);
+                if charge != 0 {
+                    write!(f, ", Charge: {:+}", charge)?;
+                }
+                if let Some(isotope) = atoms.isotope_of(aid) {
+                    write!(f, ", Isotope: {}", isotope)?;
+                }
+                if let Some(radical) = atoms.radical_of(aid) {
+                    write!(f, ", Radical: {}", radical)?;
+                }
+                writeln!(f)?;
+            }
+        } else {
+            writeln!(f, "Atoms: None")?;
+        }
+        writeln!(f, "---------------------")?;
+        if let Some(bonds) = &self.bonds {
+            writeln!(f, "Bonds:")?;
+            for (i, bond) in bonds.iter().enumerate() {
+                writeln!(
+                    f,
+                    "  Bond {}: Atom1 {}, Atom2 {}, Order {}",
+                    i + 1,
+                    bond.aid1,
+                    bond.aid2,
+                    bond.order
+                )?;
+            }
+        } else {
+            writeln!(f, "Bonds: None")?;
+        }
+        writeln!(f, "---------------------")?;
+        if let Some(coords) = &self.coords {
+            writeln!(f, "Coordinates:")?;
+            for (i, conformer) in coords.iter().flat_map(|c| c.conformers.iter()).enumerate() {
+                writeln!(f, "  Conformer {}:", i + 1)?;
+                writeln!(f, "    X = {:?}", conformer.x)?;
+                writeln!(f, "    Y = {:?}", conformer.y)?;
+                if let Some(z) = &conformer.z {
+                    writeln!(f, "    Z = {:?}", z)?;
+                }
+                if let Some(id) = conformer.id() {
+                    writeln!(f, "    Conformer ID: {}", id)?;
+                }
+                if let Some(energy) = conformer.energy() {
+                    writeln!(f, "    Energy (MMFF94): {}", energy)?;
+                }
+                if let Some(volume) = conformer.volume() {
+                    writeln!(f, "    Shape Volume: {}", volume)?;
+                }
+                if let Some(rmsd) = conformer.rmsd() {
+                    writeln!(f, "    RMSD: {}", rmsd)?;
+                }
+            }
+        } else {
+            writeln!(f, "Coordinates: None")?;
+        }
+        writeln!(f, "---------------------")?;
+        if let Some(props) = &self.props {
+            writeln!(f, "Properties:")?;
+            for prop in props {
+                writeln!(
+                    f,
+                    "  Property: {:?}, Value: {:?}",
+                    prop.urn.label, prop.value
+                )?;
+            }
+        } else {
+            writeln!(f, "Properties: None")?;
+        }
+        writeln!(f, "---------------------")?;
+        if let Some(stereo) = &self.stereo {
+            writeln!(f, "Stereo Information:")?;
+            for (i, s) in stereo.iter().enumerate() {
+                writeln!(f, "  Stereo {}: {}", i + 1, s)?;
+            }
+        } else {
+            writeln!(f, "Stereo Information: None")?;
+        }
+        writeln!(f, "---------------------")?;
+        if let Some(iupac) = &self.iupac {
+            writeln!(f, "IUPAC Names:")?;
+            for name in iupac {
+                match &name.style {
+                    Some(style) => writeln!(f, "  {}: {}", style, name.name.as_deref().unwrap_or("Unknown"))?,
+                    None => writeln!(f, "  {}", name.name.as_deref().unwrap_or("Unknown"))?,
+                }
+            }
+        } else {
+            writeln!(f, "IUPAC Names: None")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ASPIRIN: &str = include_str!("../tests/data/compound_cid_2244.json");
+    const L_ALANINE: &str = include_str!("../tests/data/compound_cid_5950.json");
+    const ASPIRIN_3D: &str = include_str!("../tests/data/compound_cid_2244_3d.json");
+    const SODIUM_ACETATE: &str = include_str!("../tests/data/compound_cid_517045.json");
+
+    #[test]
+    fn parses_pc_compounds_envelope() {
+        let compounds = parse_compounds(ASPIRIN).unwrap();
+        assert_eq!(compounds.len(), 1);
+        let aspirin = &compounds[0];
+        assert_eq!(aspirin.cid, Some(2244));
+        assert_eq!(aspirin.charge, Some(0));
+        assert_eq!(aspirin.heavy_atom_count, Some(13));
+
+        let atoms = aspirin.atoms.as_ref().unwrap();
+        assert_eq!(atoms.aid.len(), 21);
+        assert_eq!(atoms.element.len(), 21);
+        assert_eq!(atoms.element[0], 8);
+
+        let bonds = aspirin.bonds.as_ref().unwrap();
+        assert_eq!(bonds.aid1.len(), 21);
+        assert_eq!(bonds.aid2.len(), 21);
+        assert_eq!(bonds.order.len(), 21);
+
+        let coords = aspirin.coords.as_ref().unwrap();
+        assert_eq!(coords[0].conformers[0].x.len(), 21);
+        assert_eq!(aspirin.props.as_ref().unwrap().len(), 21);
+        assert!(aspirin.stereo.is_none());
+    }
+
+    #[test]
+    fn parses_tetrahedral_stereo() {
+        let compounds = parse_compounds(L_ALANINE).unwrap();
+        let alanine = &compounds[0];
+        assert_eq!(alanine.cid, Some(5950));
+        let stereo = alanine.stereo.as_ref().unwrap();
+        let Stereo::Tetrahedral(tetrahedral) = &stereo[0] else {
+            panic!("expected a tetrahedral center, got {:?}", stereo[0]);
+        };
+        assert_eq!(tetrahedral.center, 4);
+        assert_eq!(tetrahedral.parity, 1);
+        assert_eq!(stereo[0].parity(), Some(Parity::Clockwise));
+        assert_eq!(
+            stereo[0].to_string(),
+            "tetrahedral at atom 4 (above 7, top 3, bottom 5, below 6): clockwise"
+        );
+    }
+
+    #[test]
+    fn parses_every_stereo_geometry() {
+        let json = r#"[
+            {"planar": {"left": 2, "ltop": 1, "lbottom": 5, "right": 3, "rtop": 4, "rbottom": 6, "parity": 2, "type": 1}},
+            {"squareplanar": {"center": 1, "lbelow": 2, "rbelow": 3, "labove": 4, "rabove": 5, "parity": 1}},
+            {"octahedral": {"center": 1, "top": 2, "bottom": 3, "labove": 4, "lbelow": 5, "rabove": 6, "rbelow": 7}},
+            {"bipyramid": {"center": 1, "above": 2, "below": 3, "top": 4, "bottom": 5, "right": 6}},
+            {"tshape": {"center": 1, "top": 2, "bottom": 3, "above": 4}},
+            {"pentagonal": {"center": 1, "top": 2, "bottom": 3, "left": 4, "labove": 5, "lbelow": 6, "rabove": 7, "rbelow": 8}}
+        ]"#;
+        let stereo: Vec<Stereo> = serde_json::from_str(json).unwrap();
+        let names: Vec<&str> = stereo.iter().map(Stereo::name).collect();
+        assert_eq!(
+            names,
+            [
+                "planar",
+                "square planar",
+                "octahedral",
+                "trigonal bipyramidal",
+                "T-shaped",
+                "pentagonal bipyramidal"
+            ]
+        );
+        assert_eq!(stereo[0].center(), None);
+        assert_eq!(stereo[0].parity(), Some(Parity::Opposite));
+        assert_eq!(
+            stereo[0].to_string(),
+            "planar bond 2=3 (left top 1, left bottom 5, right top 4, right bottom 6): opposite sides"
+        );
+        assert_eq!(stereo[1].parity(), Some(Parity::UShape));
+        assert_eq!(stereo[2].parity(), None);
+        assert_eq!(stereo[2].atoms(), [1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(stereo[5].center(), Some(1));
+    }
+
+    #[test]
+    fn keeps_unknown_stereo_geometries() {
+        let json = r#"[
+            {"helical": {"center": 1, "turns": 2}},
+            {"tshape": {"center": 1, "top": 2, "bottom": 3, "above": 4}}
+        ]"#;
+        let stereo: Vec<Stereo> = serde_json::from_str(json).unwrap();
+        assert_eq!(stereo[0], Stereo::Unknown("helical".to_string()));
+        assert_eq!(stereo[0].center(), None);
+        assert!(stereo[0].atoms().is_empty());
+        assert_eq!(stereo[0].parity(), None);
+        assert_eq!(stereo[0].to_string(), "unknown geometry `helical`");
+        assert_eq!(stereo[1].name(), "T-shaped");
+
+        // A known geometry with missing fields is still an error, not an unknown descriptor.
+        assert!(serde_json::from_str::<Vec<Stereo>>(r#"[{"tetrahedral": {"center": 1}}]"#).is_err());
+    }
+
+    #[test]
+    fn iterates_bond_table() {
+        let compounds = parse_compounds(ASPIRIN).unwrap();
+        let bonds: Vec<Bond> = compounds[0].bonds.as_ref().unwrap().iter().collect();
+        assert_eq!(bonds.len(), 21);
+        assert_eq!(bonds[0], Bond { aid1: 1, aid2: 5, order: 1 });
+        assert_eq!(bonds[4], Bond { aid1: 3, aid2: 11, order: 2 });
+    }
+
+    #[test]
+    fn maps_aids_to_array_indices() {
+        let atoms = AtomInfo {
+            aid: vec![3, 7, 9],
+            element: vec![6, 8, 7],
+            charge: Vec::new(),
+            isotope: Vec::new(),
+            radical: Vec::new(),
+        };
+        let index = atoms.aid_index();
+        assert_eq!(index.get(&3), Some(&0));
+        assert_eq!(index.get(&9), Some(&2));
+        assert_eq!(index.get(&1), None);
+    }
+
+    #[test]
+    fn resolves_atom_elements() {
+        let atoms = AtomInfo {
+            aid: vec![1, 2, 3],
+            element: vec![6, 8, 253],
+            charge: vec![AtomInt { aid: 2, value: -1 }],
+            isotope: vec![AtomInt { aid: 1, value: 13 }],
+            radical: vec![AtomRadical { aid: 1, kind: 2 }, AtomRadical { aid: 2, kind: 255 }],
+        };
+        let symbols: Vec<Option<&str>> = atoms.elements().map(|e| e.map(|e| e.symbol)).collect();
+        assert_eq!(symbols, [Some("C"), Some("O"), None]);
+        assert_eq!(atoms.charge_of(2), -1);
+        assert_eq!(atoms.charge_of(1), 0);
+        assert_eq!(atoms.isotope_of(1), Some(13));
+        assert_eq!(atoms.isotope_of(2), None);
+        assert_eq!(atoms.radical_of(1), Some(Radical::Doublet));
+        assert_eq!(atoms.radical_of(2), None);
+        assert_eq!(Radical::Triplet.electrons(), 2);
+    }
+
+    #[test]
+    fn displays_atom_annotations() {
+        let salt = parse_compounds(SODIUM_ACETATE).unwrap().remove(0);
+        let text = salt.to_string();
+        assert!(text.contains("Atom ID: 1, Element: O, Charge: -1\n"));
+        assert!(text.contains("Atom ID: 3, Element: Na, Charge: +1\n"));
+        assert!(text.contains("Atom ID: 2, Element: O\n"));
+    }
+
+    #[test]
+    fn resolves_descriptors_from_props() {
+        let compounds = parse_compounds(ASPIRIN).unwrap();
+        let aspirin = &compounds[0];
+        assert_eq!(aspirin.molecular_formula.as_deref(), Some("C9H8O4"));
+        assert_eq!(aspirin.molecular_weight, Some(180.16));
+        assert_eq!(aspirin.exact_mass, Some(180.04225873));
+        assert_eq!(aspirin.monoisotopic_mass, Some(180.04225873));
+        assert_eq!(aspirin.inchikey.as_deref(), Some("BSYNRYMUTXBXSQ-UHFFFAOYSA-N"));
+        assert!(aspirin.inchi.as_deref().unwrap().starts_with("InChI=1S/C9H8O4/"));
+        assert_eq!(aspirin.isomeric_smiles.as_deref(), Some("CC(=O)OC1=CC=CC=C1C(=O)O"));
+        assert_eq!(aspirin.tpsa, Some(63.6));
+        assert_eq!(aspirin.xlogp, Some(1.2));
+        assert_eq!(aspirin.complexity, Some(212.0));
+        assert_eq!(aspirin.h_bond_donor_count, Some(1));
+        assert_eq!(aspirin.h_bond_acceptor_count, Some(4));
+        assert_eq!(aspirin.rotatable_bond_count, Some(3));
+
+        let iupac = aspirin.iupac.as_ref().unwrap();
+        assert_eq!(iupac[0].style.as_deref(), Some("Preferred"));
+        assert_eq!(iupac[0].name.as_deref(), Some("2-acetyloxybenzoic acid"));
+        assert!(iupac.iter().any(|n| n.name.as_deref() == Some("2-acetoxybenzoic acid")));
+    }
+
+    #[test]
+    fn resolves_stereo_smiles_and_negative_xlogp() {
+        let compounds = parse_compounds(L_ALANINE).unwrap();
+        let alanine = &compounds[0];
+        assert_eq!(alanine.isomeric_smiles.as_deref(), Some("C[C@@H](C(=O)O)N"));
+        assert_eq!(alanine.xlogp, Some(-3.0));
+    }
+
+    #[test]
+    fn looks_up_unmapped_props() {
+        let compounds = parse_compounds(ASPIRIN).unwrap();
+        let aspirin = &compounds[0];
+        let mono = aspirin.prop("Weight", Some("MonoIsotopic")).unwrap();
+        assert_eq!(mono.as_f64(), Some(180.04225873));
+        let keys = aspirin.prop("Fingerprint", Some("SubStructure Keys")).unwrap();
+        assert!(keys.binary.is_some());
+        assert!(aspirin.prop("Fingerprint", None).is_none());
+    }
+
+    #[test]
+    fn parses_3d_conformer_with_data() {
+        let compounds = parse_compounds(ASPIRIN_3D).unwrap();
+        let conformer = &compounds[0].coords.as_ref().unwrap()[0].conformers[0];
+        assert!(conformer.is_3d());
+        assert_eq!(conformer.z.as_ref().unwrap().len(), conformer.x.len());
+        assert_eq!(conformer.id(), Some("000008C400000001"));
+        assert_eq!(conformer.energy(), Some(41.356));
+        assert_eq!(conformer.volume(), Some(158.9));
+        assert_eq!(conformer.rmsd(), Some(0.6));
+        let text = compounds[0].to_string();
+        assert!(!text.contains("\\n"));
+        let block = &text[text.find("  Conformer 1:\n").unwrap()..];
+        let lines: Vec<&str> = block.lines().take(6).collect();
+        assert!(lines[1].starts_with("    X = ["));
+        assert!(lines[2].starts_with("    Y = ["));
+        assert!(lines[3].starts_with("    Z = ["));
+        assert_eq!(lines[4], "    Conformer ID: 000008C400000001");
+        assert_eq!(lines[5], "    Energy (MMFF94): 41.356");
+
+        let flat = parse_compounds(ASPIRIN).unwrap();
+        let conformer = &flat[0].coords.as_ref().unwrap()[0].conformers[0];
+        assert!(!conformer.is_3d());
+        assert!(conformer.data.is_empty());
+    }
+
+    #[test]
+    fn rejects_bare_compound_body() {
+        assert!(parse_compounds(r#"{"cid": 2244}"#).is_err());
+    }
+}
@@ -0,0 +1,290 @@
+//! Periodic table: per-element data shared by printing, plotting and mass calculations.
+
+use std::fmt;
+
+/// One chemical element.
+///
+/// Atomic weights are IUPAC conventional values; for elements without stable isotopes they are
+/// the mass number of the longest-lived isotope. The monoisotopic mass is that of the most
+/// abundant (or longest-lived) isotope. Radii are in ångströms: covalent radii from Cordero et al.
+/// (2008), van der Waals radii from Bondi (1964) with Mantina et al. (2009) for main-group gaps;
+/// `None` where no accepted value exists. Colors are the Jmol CPK scheme.
+#[derive(Debug, PartialEq)]
+pub struct Element {
+    pub number: u32,
+    pub symbol: &'static str,
+    pub name: &'static str,
+    pub atomic_weight: f64,
+    pub monoisotopic_mass: f64,
+    pub covalent_radius: Option<f64>,
+    pub vdw_radius: Option<f64>,
+    /// `0xRRGGBB`.
+    pub cpk_color: u32,
+    /// Common valences, the usual one for organic structures first; empty when none is established.
+    pub valences: &'static [u32],
+}
+
+macro_rules! element {
+    ($number:literal, $symbol:literal, $name:literal, $weight:literal, $mass:literal, $covalent:expr, $vdw:expr, $color:literal, [$($valence:literal),*]) => {
+        Element {
+            number: $number,
+            symbol: $symbol,
+            name: $name,
+            atomic_weight: $weight,
+            monoisotopic_mass: $mass,
+            covalent_radius: $covalent,
+            vdw_radius: $vdw,
+            cpk_color: $color,
+            valences: &[$($valence),*],
+        }
+    };
+}
+
+/// Every element, indexed by atomic number minus one.
+pub static ELEMENTS: [Element; 118] = [
+    element!(1, "H", "Hydrogen", 1.008, 1.00782503207, Some(0.31), Some(1.20), 0xFFFFFF, [1]),
+    element!(2, "He", "Helium", 4.0026, 4.00260325415, Some(0.28), Some(1.40), 0xD9FFFF, [0]),
+    element!(3, "Li", "Lithium", 6.94, 7.016004548, Some(1.28), Some(1.82), 0xCC80FF, [1]),
+    element!(4, "Be", "Beryllium", 9.0122, 9.012182201, Some(0.96), Some(1.53), 0xC2FF00, [2]),
+    element!(5, "B", "Boron", 10.81, 11.009305406, Some(0.84), Some(1.92), 0xFFB5B5, [3]),
+    element!(6, "C", "Carbon", 12.011, 12.0, Some(0.76), Some(1.70), 0x909090, [4]),
+    element!(7, "N", "Nitrogen", 14.007, 14.00307400478, Some(0.71), Some(1.55), 0x3050F8, [3, 5]),
+    element!(8, "O", "Oxygen", 15.999, 15.99491461956, Some(0.66), Some(1.52), 0xFF0D0D, [2]),
+    element!(9, "F", "Fluorine", 18.998, 18.998403224, Some(0.57), Some(1.47), 0x90E050, [1]),
+    element!(10, "Ne", "Neon", 20.180, 19.99244017542, Some(0.58), Some(1.54), 0xB3E3F5, [0]),
+    element!(11, "Na", "Sodium", 22.990, 22.98976966, Some(1.66), Some(2.27), 0xAB5CF2, [1]),
+    element!(12, "Mg", "Magnesium", 24.305, 23.985041699, Some(1.41), Some(1.73), 0x8AFF00, [2]),
+    element!(13, "Al", "Aluminium", 26.982, 26.981538627, Some(1.21), Some(1.84), 0xBFA6A6, [3]),
+    element!(14, "Si", "Silicon", 28.085, 27.97692653246, Some(1.11), Some(2.10), 0xF0C8A0, [4]),
+    element!(15, "P", "Phosphorus", 30.974, 30.973761629, Some(1.07), Some(1.80), 0xFF8000, [3, 5]),
+    element!(16, "S", "Sulfur", 32.06, 31.972070999, Some(1.05), Some(1.80), 0xFFFF30, [2, 4, 6]),
+    element!(17, "Cl", "Chlorine", 35.45, 34.968852682, Some(1.02), Some(1.75), 0x1FF01F, [1, 3, 5, 7]),
+    element!(18, "Ar", "Argon", 39.95, 39.96238312251, Some(1.06), Some(1.88), 0x80D1E3, [0]),
+    element!(19, "K", "Potassium", 39.098, 38.963706679, Some(2.03), Some(2.75), 0x8F40D4, [1]),
+    element!(20, "Ca", "Calcium", 40.078, 39.962590983, Some(1.76), Some(2.31), 0x3DFF00, [2]),
+    element!(21, "Sc", "Scandium", 44.956, 44.955911909, Some(1.70), None, 0xE6E6E6, [3]),
+    element!(22, "Ti", "Titanium", 47.867, 47.947946281, Some(1.60), None, 0xBFC2C7, [4, 3, 2]),
+    element!(23, "V", "Vanadium", 50.942, 50.943959507, Some(1.53), None, 0xA6A6AB, [5, 4, 3, 2]),
+    element!(24, "Cr", "Chromium", 51.996, 51.940507472, Some(1.39), None, 0x8A99C7, [3, 6, 2]),
+    element!(25, "Mn", "Manganese", 54.938, 54.938045141, Some(1.39), None, 0x9C7AC7, [2, 4, 7, 3, 6]),
+    element!(26, "Fe", "Iron", 55.845, 55.934937475, Some(1.32), None, 0xE06633, [2, 3]),
+    element!(27, "Co", "Cobalt", 58.933, 58.933195048, Some(1.26), None, 0xF090A0, [2, 3]),
+    element!(28, "Ni", "Nickel", 58.693, 57.935342907, Some(1.24), Some(1.63), 0x50D050, [2]),
+    element!(29, "Cu", "Copper", 63.546, 62.929597474, Some(1.32), Some(1.40), 0xC88033, [2, 1]),
+    element!(30, "Zn", "Zinc", 65.38, 63.929142222, Some(1.22), Some(1.39), 0x7D80B0, [2]),
+    element!(31, "Ga", "Gallium", 69.723, 68.925573587, Some(1.22), Some(1.87), 0xC28F8F, [3]),
+    element!(32, "Ge", "Germanium", 72.630, 73.921177767, Some(1.20), Some(2.11), 0x668F8F, [4]),
+    element!(33, "As", "Arsenic", 74.922, 74.921596478, Some(1.19), Some(1.85), 0xBD80E3, [3, 5]),
+    element!(34, "Se", "Selenium", 78.971, 79.916521271, Some(1.20), Some(1.90), 0xFFA100, [2, 4, 6]),
+    element!(35, "Br", "Bromine", 79.904, 78.918337087, Some(1.20), Some(1.85), 0xA62929, [1, 3, 5]),
+    element!(36, "Kr", "Krypton", 83.798, 83.911507, Some(1.16), Some(2.02), 0x5CB8D1, [0, 2]),
+    element!(37, "Rb", "Rubidium", 85.468, 84.911789737, Some(2.20), Some(3.03), 0x702EB0, [1]),
+    element!(38, "Sr", "Strontium", 87.62, 87.905612124, Some(1.95), Some(2.49), 0x00FF00, [2]),
+    element!(39, "Y", "Yttrium", 88.906, 88.905848295, Some(1.90), None, 0x94FFFF, [3]),
+    element!(40, "Zr", "Zirconium", 91.224, 89.904704416, Some(1.75), None, 0x94E0E0, [4]),
+    element!(41, "Nb", "Niobium", 92.906, 92.906378058, Some(1.64), None, 0x73C2C9, [5, 3]),
+    element!(42, "Mo", "Molybdenum", 95.95, 97.905408169, Some(1.54), None, 0x54B5B5, [6, 4]),
+    element!(43, "Tc", "Technetium", 98.0, 97.907216, Some(1.47), None, 0x3B9E9E, [7, 4]),
+    element!(44, "Ru", "Ruthenium", 101.07, 101.904349312, Some(1.46), None, 0x248F8F, [3, 4, 8]),
+    element!(45, "Rh", "Rhodium", 102.91, 102.905504292, Some(1.42), None, 0x0A7D8C, [3]),
+    element!(46, "Pd", "Palladium", 106.42, 105.903485715, Some(1.39), Some(1.63), 0x006985, [2, 4]),
+    element!(47, "Ag", "Silver", 107.87, 106.905096820, Some(1.45), Some(1.72), 0xC0C0C0, [1]),
+    element!(48, "Cd", "Cadmium", 112.41, 113.903358540, Some(1.44), Some(1.58), 0xFFD98F, [2]),
+    element!(49, "In", "Indium", 114.82, 114.903878484, Some(1.42), Some(1.93), 0xA67573, [3]),
+    element!(50, "Sn", "Tin", 118.71, 119.902194676, Some(1.39), Some(2.17), 0x668080, [4, 2]),
+    element!(51, "Sb", "Antimony", 121.76, 120.903815686, Some(1.39), Some(2.06), 0x9E63B5, [3, 5]),
+    element!(52, "Te", "Tellurium", 127.60, 129.906224399, Some(1.38), Some(2.06), 0xD47A00, [2, 4, 6]),
+    element!(53, "I", "Iodine", 126.90, 126.904472681, Some(1.39), Some(1.98), 0x940094, [1, 3, 5, 7]),
+    element!(54, "Xe", "Xenon", 131.29, 131.904153457, Some(1.40), Some(2.16), 0x429EB0, [0, 2, 4, 6]),
+    element!(55, "Cs", "Caesium", 132.91, 132.905451932, Some(2.44), Some(3.43), 0x57178F, [1]),
+    element!(56, "Ba", "Barium", 137.33, 137.905247237, Some(2.15), Some(2.68), 0x00C900, [2]),
+    element!(57, "La", "Lanthanum", 138.91, 138.906353267, Some(2.07), None, 0x70D4FF, [3]),
+    element!(58, "Ce", "Cerium", 140.12, 139.905438706, Some(2.04), None, 0xFFFFC7, [3, 4]),
+    element!(59, "Pr", "Praseodymium", 140.91, 140.907652769, Some(2.03), None, 0xD9FFC7, [3]),
+    element!(60, "Nd", "Neodymium", 144.24, 141.907723297, Some(2.01), None, 0xC7FFC7, [3]),
+    element!(61, "Pm", "Promethium", 145.0, 144.912749, Some(1.99), None, 0xA3FFC7, [3]),
+    element!(62, "Sm", "Samarium", 150.36, 151.919732425, Some(1.98), None, 0x8FFFC7, [3, 2]),
+    element!(63, "Eu", "Europium", 151.96, 152.921230339, Some(1.98), None, 0x61FFC7, [3, 2]),
+    element!(64, "Gd", "Gadolinium", 157.25, 157.924103912, Some(1.96), None, 0x45FFC7, [3]),
+    element!(65, "Tb", "Terbium", 158.93, 158.925346757, Some(1.94), None, 0x30FFC7, [3]),
+    element!(66, "Dy", "Dysprosium", 162.50, 163.929174751, Some(1.92), None, 0x1FFFC7, [3]),
+    element!(67, "Ho", "Holmium", 164.93, 164.930322070, Some(1.92), None, 0x00FF9C, [3]),
+    element!(68, "Er", "Erbium", 167.26, 165.930293061, Some(1.89), None, 0x00E675, [3]),
+    element!(69, "Tm", "Thulium", 168.93, 168.934213250, Some(1.90), None, 0x00D452, [3]),
+    element!(70, "Yb", "Ytterbium", 173.05, 173.938862089, Some(1.87), None, 0x00BF38, [3, 2]),
+    element!(71, "Lu", "Lutetium", 174.97, 174.940771819, Some(1.87), None, 0x00AB24, [3]),
+    element!(72, "Hf", "Hafnium", 178.49, 179.946549953, Some(1.75), None, 0x4DC2FF, [4]),
+    element!(73, "Ta", "Tantalum", 180.95, 180.947995763, Some(1.70), None, 0x4DA6FF, [5]),
+    element!(74, "W", "Tungsten", 183.84, 183.950931188, Some(1.62), None, 0x2194D6, [6, 4]),
+    element!(75, "Re", "Rhenium", 186.21, 186.955753109, Some(1.51), None, 0x267DAB, [7, 4]),
+    element!(76, "Os", "Osmium", 190.23, 191.961480690, Some(1.44), None, 0x266696, [4, 8]),
+    element!(77, "Ir", "Iridium", 192.22, 192.962926430, Some(1.41), None, 0x175487, [3, 4]),
+    element!(78, "Pt", "Platinum", 195.08, 194.964791134, Some(1.36), Some(1.75), 0xD0D0E0, [2, 4]),
+    element!(79, "Au", "Gold", 196.97, 196.966568662, Some(1.36), Some(1.66), 0xFFD123, [3, 1]),
+    element!(80, "Hg", "Mercury", 200.59, 201.970643011, Some(1.32), Some(1.55), 0xB8B8D0, [2, 1]),
+    element!(81, "Tl", "Thallium", 204.38, 204.974427541, Some(1.45), Some(1.96), 0xA6544D, [1, 3]),
+    element!(82, "Pb", "Lead", 207.2, 207.976652071, Some(1.46), Some(2.02), 0x575961, [2, 4]),
+    element!(83, "Bi", "Bismuth", 208.98, 208.980398734, Some(1.48), Some(2.07), 0x9E4FB5, [3, 5]),
+    element!(84, "Po", "Polonium", 209.0, 208.982430, Some(1.40), Some(1.97), 0xAB5C00, [2, 4]),
+    element!(85, "At", "Astatine", 210.0, 209.987148, Some(1.50), Some(2.02), 0x754F45, [1]),
+    element!(86, "Rn", "Radon", 222.0, 222.017578, Some(1.50), Some(2.20), 0x428296, [0]),
+    element!(87, "Fr", "Francium", 223.0, 223.019736, Some(2.60), Some(3.48), 0x420066, [1]),
+    element!(88, "Ra", "Radium", 226.0, 226.025410, Some(2.21), Some(2.83), 0x007D00, [2]),
+    element!(89, "Ac", "Actinium", 227.0, 227.027752, Some(2.15), None, 0x70ABFA, [3]),
+    element!(90, "Th", "Thorium", 232.04, 232.038055325, Some(2.06), None, 0x00BAFF, [4]),
+    element!(91, "Pa", "Protactinium", 231.04, 231.035883990, Some(2.00), None, 0x00A1FF, [5]),
+    element!(92, "U", "Uranium", 238.03, 238.050788247, Some(1.96), Some(1.86), 0x008FFF, [6, 4]),
+    element!(93, "Np", "Neptunium", 237.0, 237.048173, Some(1.90), None, 0x0080FF, [5]),
+    element!(94, "Pu", "Plutonium", 244.0, 244.064204, Some(1.87), None, 0x006BFF, [4]),
+    element!(95, "Am", "Americium", 243.0, 243.061381, Some(1.80), None, 0x545CF2, [3]),
+    element!(96, "Cm", "Curium", 247.0, 247.070354, Some(1.69), None, 0x785CE3, [3]),
+    element!(97, "Bk", "Berkelium", 247.0, 247.070307, None, None, 0x8A4FE3, [3]),
+    element!(98, "Cf", "Californium", 251.0, 251.079587, None, None, 0xA136D4, [3]),
+    element!(99, "Es", "Einsteinium", 252.0, 252.082980, None, None, 0xB31FD4, [3]),
+    element!(100, "Fm", "Fermium", 257.0, 257.095105, None, None, 0xB31FBA, [3]),
+    element!(101, "Md", "Mendelevium", 258.0, 258.098431, None, None, 0xB30DA6, [3]),
+    element!(102, "No", "Nobelium", 259.0, 259.10103, None, None, 0xBD0D87, [2]),
+    element!(103, "Lr", "Lawrencium", 262.0, 262.10963, None, None, 0xC70066, [3]),
+    element!(104, "Rf", "Rutherfordium", 267.0, 267.122, None, None, 0xCC0059, []),
+    element!(105, "Db", "Dubnium", 268.0, 268.126, None, None, 0xD1004F, []),
+    element!(106, "Sg", "Seaborgium", 269.0, 269.129, None, None, 0xD90045, []),
+    element!(107, "Bh", "Bohrium", 270.0, 270.133, None, None, 0xE00038, []),
+    element!(108, "Hs", "Hassium", 269.0, 269.134, None, None, 0xE6002E, []),
+    element!(109, "Mt", "Meitnerium", 278.0, 278.156, None, None, 0xEB0026, []),
+    element!(110, "Ds", "Darmstadtium", 281.0, 281.165, None, None, 0xFF1493, []),
+    element!(111, "Rg", "Roentgenium", 282.0, 282.169, None, None, 0xFF1493, []),
+    element!(112, "Cn", "Copernicium", 285.0, 285.177, None, None, 0xFF1493, []),
+    element!(113, "Nh", "Nihonium", 286.0, 286.182, None, None, 0xFF1493, []),
+    element!(114, "Fl", "Flerovium", 289.0, 289.190, None, None, 0xFF1493, []),
+    element!(115, "Mc", "Moscovium", 290.0, 290.196, None, None, 0xFF1493, []),
+    element!(116, "Lv", "Livermorium", 293.0, 293.205, None, None, 0xFF1493, []),
+    element!(117, "Ts", "Tennessine", 294.0, 294.211, None, None, 0xFF1493, []),
+    element!(118, "Og", "Oganesson", 294.0, 294.214, None, None, 0xFF1493, []),
+];
+
+/// Exact masses (AME2016) of the isotopes PubChem records commonly label: stable tracers, deuterium
+/// and tritium, and the usual PET and radiolabel nuclides, as `(atomic number, mass number, mass)`.
+static ISOTOPE_MASSES: &[(u32, u32, f64)] = &[
+    (1, 2, 2.01410177812),
+    (1, 3, 3.0160492779),
+    (3, 6, 6.0151228874),
+    (5, 10, 10.012936862),
+    (6, 11, 11.0114336),
+    (6, 13, 13.00335483507),
+    (6, 14, 14.0032419884),
+    (7, 13, 13.00573861),
+    (7, 15, 15.00010889888),
+    (8, 15, 15.0030656),
+    (8, 17, 16.99913175650),
+    (8, 18, 17.99915961286),
+    (9, 18, 18.0009380),
+    (14, 29, 28.9764946649),
+    (14, 30, 29.973770136),
+    (15, 32, 31.97390764),
+    (15, 33, 32.9717257),
+    (16, 33, 32.9714589098),
+    (16, 34, 33.967867004),
+    (16, 35, 34.96903231),
+    (17, 36, 35.96830682),
+    (17, 37, 36.965902602),
+    (35, 81, 80.9162897),
+    (53, 123, 122.905589),
+    (53, 124, 123.9062090),
+    (53, 125, 124.9046294),
+    (53, 131, 130.9061263),
+];
+
+impl Element {
+    /// Looks up an element by atomic number. PubChem's pseudo-elements (252 lone pair, 253 R group,
+    /// 254 dummy, 255 unspecified) have no entry.
+    pub fn from_number(number: u32) -> Option<&'static Element> {
+        let index = usize::try_from(number).ok()?.checked_sub(1)?;
+        ELEMENTS.get(index)
+    }
+
+    /// Looks up an element by symbol, case-insensitively.
+    pub fn from_symbol(symbol: &str) -> Option<&'static Element> {
+        ELEMENTS
+            .iter()
+            .find(|element| element.symbol.eq_ignore_ascii_case(symbol))
+    }
+
+    pub fn cpk_rgb(&self) -> (u8, u8, u8) {
+        let [_, r, g, b] = self.cpk_color.to_be_bytes();
+        (r, g, b)
+    }
+
+    /// Mass of this element's isotope with `mass_number`. Isotopes missing from the built-in table
+    /// are estimated by carrying the main isotope's mass defect over, which is off by a few
+    /// thousandths of a unit for neighbouring isotopes.
+    pub fn isotope_mass(&self, mass_number: u32) -> f64 {
+        if let Some(&(_, _, mass)) = ISOTOPE_MASSES
+            .iter()
+            .find(|&&(number, isotope, _)| number == self.number && isotope == mass_number)
+        {
+            return mass;
+        }
+        let main = self.monoisotopic_mass.round();
+        if mass_number as f64 == main {
+            self.monoisotopic_mass
+        } else {
+            mass_number as f64 + (self.monoisotopic_mass - main)
+        }
+    }
+
+    /// The usual valence for implicit-hydrogen counting, if the element has one.
+    pub fn default_valence(&self) -> Option<u32> {
+        self.valences.first().copied()
+    }
+}
+
+impl fmt::Display for Element {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.symbol)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_is_ordered_by_atomic_number() {
+        for (index, element) in ELEMENTS.iter().enumerate() {
+            assert_eq!(element.number as usize, index + 1, "{}", element.name);
+            assert!(element.atomic_weight > 0.0 && element.monoisotopic_mass > 0.0);
+        }
+    }
+
+    #[test]
+    fn looks_up_by_number_and_symbol() {
+        let carbon = Element::from_number(6).unwrap();
+        assert_eq!(carbon.symbol, "C");
+        assert_eq!(carbon.to_string(), "C");
+        assert_eq!(carbon.monoisotopic_mass, 12.0);
+        assert_eq!(Element::from_symbol("cl").unwrap().number, 17);
+        assert_eq!(Element::from_number(118).unwrap().name, "Oganesson");
+        assert!(Element::from_number(0).is_none());
+        assert!(Element::from_number(255).is_none());
+        assert!(Element::from_symbol("Xx").is_none());
+    }
+
+    #[test]
+    fn exposes_colors_and_valences() {
+        let oxygen = Element::from_symbol("O").unwrap();
+        assert_eq!(oxygen.cpk_rgb(), (0xFF, 0x0D, 0x0D));
+        assert_eq!(oxygen.default_valence(), Some(2));
+        assert_eq!(Element::from_symbol("S").unwrap().valences, [2, 4, 6]);
+        assert_eq!(Element::from_symbol("Og").unwrap().default_valence(), None);
+    }
+
+    #[test]
+    fn resolves_isotope_masses() {
+        let hydrogen = Element::from_number(1).unwrap();
+        assert_eq!(hydrogen.isotope_mass(1), hydrogen.monoisotopic_mass);
+        assert_eq!(hydrogen.isotope_mass(2), 2.01410177812);
+        let cobalt = Element::from_symbol("Co").unwrap();
+        let estimate = cobalt.isotope_mass(60);
+        assert!((estimate - 59.9338171).abs() < 0.01, "{}", estimate);
+    }
+}
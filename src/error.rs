@@ -0,0 +1,224 @@
+//! Errors returned by [`PubChemClient`](crate::PubChemClient).
+
+use serde::Deserialize;
+use std::fmt;
+
+pub type Result<T> = std::result::Result<T, PcPugError>;
+
+/// PUG REST's error body: `{"Fault": {"Code": ..., "Message": ..., "Details": [...]}}`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Fault {
+    #[serde(rename = "Code")]
+    pub code: String,
+    #[serde(rename = "Message", default)]
+    pub message: String,
+    #[serde(rename = "Details", default)]
+    pub details: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FaultEnvelope {
+    #[serde(rename = "Fault")]
+    fault: Fault,
+}
+
+impl Fault {
+    pub fn new(code: &str, message: impl Into<String>) -> Self {
+        Fault {
+            code: code.to_string(),
+            message: message.into(),
+            details: Vec::new(),
+        }
+    }
+
+    /// Parses a fault body, falling back to one derived from the HTTP status when the
+    /// body is missing or not PUG REST JSON (e.g. an HTML page from a proxy).
+    pub fn from_response(status: u16, body: &str) -> Self {
+        match serde_json::from_str::<FaultEnvelope>(body) {
+            Ok(envelope) => envelope.fault,
+            Err(_) => Fault::new(default_code(status), format!("HTTP status {}", status)),
+        }
+    }
+}
+
+fn default_code(status: u16) -> &'static str {
+    match status {
+        400 => "PUGREST.BadRequest",
+        404 => "PUGREST.NotFound",
+        405 => "PUGREST.NotAllowed",
+        501 => "PUGREST.Unimplemented",
+        503 => "PUGREST.ServerBusy",
+        504 => "PUGREST.Timeout",
+        500 => "PUGREST.ServerError",
+        _ => "PUGREST.Unknown",
+    }
+}
+
+impl fmt::Display for Fault {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.code, self.message)?;
+        if !self.details.is_empty() {
+            write!(f, " ({})", self.details.join("; "))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum PcPugError {
+    /// `PUGREST.NotFound`: the identifier matched no records.
+    NotFound(Fault),
+    /// `PUGREST.BadRequest` or `PUGREST.NotAllowed`: the request itself is malformed.
+    BadRequest(Fault),
+    /// `PUGREST.ServerBusy` (HTTP 503): too many requests, try again later.
+    ServerBusy(Fault),
+    /// `PUGREST.Timeout` (HTTP 504), or the request timed out on our side.
+    Timeout(Fault),
+    /// Any other fault PubChem reports, e.g. `PUGREST.ServerError`.
+    Server { status: u16, fault: Fault },
+    /// Connection, TLS or other transport failure.
+    Http(reqwest::Error),
+    /// The response body was not the JSON we expected.
+    Decode(serde_json::Error),
+    /// The request was rejected before being sent, e.g. an unsupported namespace for the operation.
+    InvalidInput(String),
+    /// Offline mode is on and the response cache has no entry for this request.
+    NotCached(String),
+}
+
+impl PcPugError {
+    /// Maps a non-success response onto the matching variant.
+    pub fn from_response(status: u16, body: &str) -> Self {
+        let fault = Fault::from_response(status, body);
+        // PUG REST and PUG View share fault names under different prefixes (PUGREST./PUGVIEW.).
+        let name = fault.code.split_once('.').map_or(fault.code.as_str(), |(_, name)| name);
+        match name {
+            "NotFound" => PcPugError::NotFound(fault),
+            "BadRequest" | "NotAllowed" => PcPugError::BadRequest(fault),
+            "ServerBusy" => PcPugError::ServerBusy(fault),
+            "Timeout" => PcPugError::Timeout(fault),
+            _ => PcPugError::Server { status, fault },
+        }
+    }
+
+    pub fn fault(&self) -> Option<&Fault> {
+        match self {
+            PcPugError::NotFound(fault)
+            | PcPugError::BadRequest(fault)
+            | PcPugError::ServerBusy(fault)
+            | PcPugError::Timeout(fault)
+            | PcPugError::Server { fault, .. } => Some(fault),
+            PcPugError::Http(_)
+            | PcPugError::Decode(_)
+            | PcPugError::InvalidInput(_)
+            | PcPugError::NotCached(_) => None,
+        }
+    }
+
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, PcPugError::NotFound(_))
+    }
+
+    /// Whether the same request may succeed if sent again later.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            PcPugError::ServerBusy(_) | PcPugError::Timeout(_) | PcPugError::Http(_) => true,
+            // 501 is PUGREST.Unimplemented, which no amount of retrying will fix.
+            PcPugError::Server { status, .. } => *status >= 500 && *status != 501,
+            PcPugError::NotFound(_)
+            | PcPugError::BadRequest(_)
+            | PcPugError::Decode(_)
+            | PcPugError::InvalidInput(_)
+            | PcPugError::NotCached(_) => false,
+        }
+    }
+}
+
+impl fmt::Display for PcPugError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PcPugError::NotFound(fault) => write!(f, "not found: {}", fault),
+            PcPugError::BadRequest(fault) => write!(f, "bad request: {}", fault),
+            PcPugError::ServerBusy(fault) => write!(f, "server busy: {}", fault),
+            PcPugError::Timeout(fault) => write!(f, "timed out: {}", fault),
+            PcPugError::Server { status, fault } => write!(f, "server error (HTTP {}): {}", status, fault),
+            PcPugError::Http(err) => write!(f, "HTTP error: {}", err),
+            PcPugError::Decode(err) => write!(f, "could not decode response: {}", err),
+            PcPugError::InvalidInput(message) => write!(f, "invalid input: {}", message),
+            PcPugError::NotCached(request) => write!(f, "offline and not in cache: {}", request),
+        }
+    }
+}
+
+impl std::error::Error for PcPugError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PcPugError::Http(err) => Some(err),
+            PcPugError::Decode(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for PcPugError {
+    fn from(err: reqwest::Error) -> Self {
+        if err.is_timeout() {
+            PcPugError::Timeout(Fault::new("PUGREST.Timeout", err.to_string()))
+        } else {
+            PcPugError::Http(err)
+        }
+    }
+}
+
+impl From<serde_json::Error> for PcPugError {
+    fn from(err: serde_json::Error) -> Self {
+        PcPugError::Decode(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOT_FOUND: &str = include_str!("../tests/data/fault_not_found.json");
+
+    #[test]
+    fn parses_not_found_fault() {
+        let err = PcPugError::from_response(404, NOT_FOUND);
+        assert!(err.is_not_found());
+        let fault = err.fault().unwrap();
+        assert_eq!(fault.code, "PUGREST.NotFound");
+        assert_eq!(fault.message, "No CID found");
+        assert_eq!(fault.details, vec!["No CID found that matches the given name"]);
+    }
+
+    #[test]
+    fn classifies_fault_codes() {
+        let body = |code: &str| format!(r#"{{"Fault": {{"Code": "{}", "Message": "m"}}}}"#, code);
+        assert!(matches!(PcPugError::from_response(400, &body("PUGREST.BadRequest")), PcPugError::BadRequest(_)));
+        assert!(matches!(PcPugError::from_response(503, &body("PUGREST.ServerBusy")), PcPugError::ServerBusy(_)));
+        assert!(matches!(PcPugError::from_response(504, &body("PUGREST.Timeout")), PcPugError::Timeout(_)));
+        assert!(PcPugError::from_response(404, &body("PUGVIEW.NotFound")).is_not_found());
+        assert!(matches!(
+            PcPugError::from_response(500, &body("PUGREST.ServerError")),
+            PcPugError::Server { status: 500, .. }
+        ));
+    }
+
+    #[test]
+    fn only_transient_errors_are_retryable() {
+        assert!(PcPugError::from_response(503, "").is_retryable());
+        assert!(PcPugError::from_response(504, "").is_retryable());
+        assert!(PcPugError::from_response(500, "").is_retryable());
+        assert!(!PcPugError::from_response(501, "").is_retryable());
+        assert!(!PcPugError::from_response(404, "").is_retryable());
+        assert!(!PcPugError::from_response(400, "").is_retryable());
+    }
+
+    #[test]
+    fn falls_back_to_status_without_fault_body() {
+        let err = PcPugError::from_response(503, "<html>Service Unavailable</html>");
+        assert!(matches!(err, PcPugError::ServerBusy(_)));
+        assert_eq!(err.fault().unwrap().message, "HTTP status 503");
+    }
+}
@@ -0,0 +1,93 @@
+//! PUG REST output formats (the trailing `<output specification>` path segment).
+
+use std::fmt;
+use std::str::FromStr;
+
+/// Format PubChem renders a response in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OutputFormat {
+    Json,
+    Xml,
+    Sdf,
+    Csv,
+    Txt,
+    Png,
+    Asnt,
+}
+
+impl OutputFormat {
+    pub const ALL: [OutputFormat; 7] = [
+        OutputFormat::Json,
+        OutputFormat::Xml,
+        OutputFormat::Sdf,
+        OutputFormat::Csv,
+        OutputFormat::Txt,
+        OutputFormat::Png,
+        OutputFormat::Asnt,
+    ];
+
+    /// Path segment PUG REST uses for this format.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OutputFormat::Json => "JSON",
+            OutputFormat::Xml => "XML",
+            OutputFormat::Sdf => "SDF",
+            OutputFormat::Csv => "CSV",
+            OutputFormat::Txt => "TXT",
+            OutputFormat::Png => "PNG",
+            OutputFormat::Asnt => "ASNT",
+        }
+    }
+
+    /// File extension for saved output.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            OutputFormat::Json => "json",
+            OutputFormat::Xml => "xml",
+            OutputFormat::Sdf => "sdf",
+            OutputFormat::Csv => "csv",
+            OutputFormat::Txt => "txt",
+            OutputFormat::Png => "png",
+            OutputFormat::Asnt => "asnt",
+        }
+    }
+
+    /// CSV and TXT only exist for tabular operations such as `property/...`, `cids` or `synonyms`.
+    pub fn supports_full_records(&self) -> bool {
+        !matches!(self, OutputFormat::Csv | OutputFormat::Txt)
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        OutputFormat::ALL
+            .into_iter()
+            .find(|format| format.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                let names: Vec<&str> = OutputFormat::ALL.iter().map(OutputFormat::as_str).collect();
+                format!("unknown output format '{}' (expected one of: {})", s, names.join(", "))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_format_names() {
+        for format in OutputFormat::ALL {
+            assert_eq!(format.as_str().parse::<OutputFormat>(), Ok(format));
+            assert_eq!(format.extension().parse::<OutputFormat>(), Ok(format));
+        }
+        assert!("mol2".parse::<OutputFormat>().is_err());
+    }
+}
@@ -0,0 +1,314 @@
+//! Molecular formula and masses computed from a structure's atoms, and cross-checks against the
+//! values PubChem reports.
+
+use crate::compound::Compound;
+use crate::element::Element;
+use crate::molecule::Molecule;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Largest difference from PubChem's molecular weight, which it rounds to two decimals, still
+/// counted as agreement.
+pub const WEIGHT_TOLERANCE: f64 = 0.01;
+/// Largest difference from PubChem's monoisotopic mass still counted as agreement.
+pub const MONOISOTOPIC_MASS_TOLERANCE: f64 = 0.001;
+
+/// Element counts in Hill order plus the net charge.
+///
+/// Labelled isotopes count under their element, as in PubChem's formulas (`H2O` for heavy water);
+/// they only change the masses.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Formula {
+    pub counts: Vec<(&'static Element, u32)>,
+    pub charge: i32,
+}
+
+impl Formula {
+    /// Counts every atom of `molecule` that is a real element, plus its implicit hydrogens.
+    pub fn from_molecule(molecule: &Molecule) -> Self {
+        let mut counts: HashMap<u32, u32> = HashMap::new();
+        let mut charge = 0;
+        for atom in molecule.atoms() {
+            charge += atom.charge;
+            if atom.element.is_some() {
+                *counts.entry(atom.atomic_number).or_default() += 1;
+            }
+            let implicit = molecule.implicit_hydrogens(atom.aid);
+            if implicit > 0 {
+                *counts.entry(1).or_default() += implicit;
+            }
+        }
+        let mut counts: Vec<(&'static Element, u32)> = counts
+            .into_iter()
+            .filter_map(|(number, count)| Some((Element::from_number(number)?, count)))
+            .collect();
+        // Hill order: carbon, then hydrogen, then the rest alphabetically; without carbon,
+        // everything (hydrogen included) is alphabetical.
+        let has_carbon = counts.iter().any(|(element, _)| element.number == 6);
+        counts.sort_by_key(|(element, _)| match element.number {
+            6 if has_carbon => (0, element.symbol),
+            1 if has_carbon => (1, element.symbol),
+            _ => (2, element.symbol),
+        });
+        Formula { counts, charge }
+    }
+
+    pub fn count(&self, symbol: &str) -> u32 {
+        self.counts
+            .iter()
+            .find(|(element, _)| element.symbol == symbol)
+            .map_or(0, |&(_, count)| count)
+    }
+}
+
+/// PubChem notation: `C9H8O4`, `C2H3O2-`, `Ca+2`.
+impl fmt::Display for Formula {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (element, count) in &self.counts {
+            f.write_str(element.symbol)?;
+            if *count > 1 {
+                write!(f, "{}", count)?;
+            }
+        }
+        match self.charge {
+            0 => Ok(()),
+            1 => f.write_str("+"),
+            -1 => f.write_str("-"),
+            charge if charge > 0 => write!(f, "+{}", charge),
+            charge => write!(f, "{}", charge),
+        }
+    }
+}
+
+impl Molecule {
+    pub fn formula(&self) -> Formula {
+        Formula::from_molecule(self)
+    }
+
+    /// Molecular weight from standard atomic weights; labelled atoms use their isotope's mass.
+    pub fn average_mass(&self) -> f64 {
+        self.mass(|element| element.atomic_weight)
+    }
+
+    /// Sum of the most abundant isotope's mass for every atom; labelled atoms use their isotope's
+    /// mass. PubChem reports this as `Weight`/`MonoIsotopic`; its exact mass is that of the most
+    /// likely isotopic composition instead, about 2 u higher once a molecule has two bromines.
+    pub fn monoisotopic_mass(&self) -> f64 {
+        self.mass(|element| element.monoisotopic_mass)
+    }
+
+    fn mass(&self, unlabelled: impl Fn(&Element) -> f64) -> f64 {
+        let hydrogen = Element::from_number(1).map_or(0.0, &unlabelled);
+        self.atoms()
+            .iter()
+            .map(|atom| {
+                let own = match (atom.element, atom.isotope) {
+                    (Some(element), Some(isotope)) => element.isotope_mass(isotope),
+                    (Some(element), None) => unlabelled(element),
+                    (None, _) => 0.0,
+                };
+                own + hydrogen * self.implicit_hydrogens(atom.aid) as f64
+            })
+            .sum()
+    }
+}
+
+/// A PubChem-reported descriptor that doesn't match the locally computed value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Discrepancy {
+    pub property: &'static str,
+    pub reported: String,
+    pub computed: String,
+}
+
+impl fmt::Display for Discrepancy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}: PubChem reports {}, computed {}",
+            self.property, self.reported, self.computed
+        )
+    }
+}
+
+impl Compound {
+    /// Recomputes the formula, molecular weight and monoisotopic mass from the atom list and returns
+    /// every PubChem value that disagrees. Records without atoms, or without a reported value, have
+    /// nothing to check.
+    pub fn verify_composition(&self) -> Vec<Discrepancy> {
+        let Some(molecule) = self.molecule() else {
+            return Vec::new();
+        };
+        let mut discrepancies = Vec::new();
+        if let Some(reported) = &self.molecular_formula {
+            let computed = molecule.formula().to_string();
+            if *reported != computed {
+                discrepancies.push(Discrepancy {
+                    property: "Molecular Formula",
+                    reported: reported.clone(),
+                    computed,
+                });
+            }
+        }
+        if let Some(reported) = self.molecular_weight {
+            let computed = molecule.average_mass();
+            if (reported - computed).abs() > WEIGHT_TOLERANCE {
+                discrepancies.push(Discrepancy {
+                    property: "Molecular Weight",
+                    reported: reported.to_string(),
+                    computed: format!("{:.2}", computed),
+                });
+            }
+        }
+        if let Some(reported) = self.monoisotopic_mass {
+            let computed = molecule.monoisotopic_mass();
+            if (reported - computed).abs() > MONOISOTOPIC_MASS_TOLERANCE {
+                discrepancies.push(Discrepancy {
+                    property: "Monoisotopic Mass",
+                    reported: reported.to_string(),
+                    computed: format!("{:.8}", computed),
+                });
+            }
+        }
+        discrepancies
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compound::{parse_compounds, AtomInfo, AtomInt, AtomRadical, BondInfo};
+
+    const ASPIRIN: &str = include_str!("../tests/data/compound_cid_2244.json");
+    const SODIUM_ACETATE: &str = include_str!("../tests/data/compound_cid_517045.json");
+    const SODIUM_BOROHYDRIDE: &str = include_str!("../tests/data/compound_cid_4311764.json");
+    const HYDRON: &str = include_str!("../tests/data/compound_cid_1038.json");
+    const DIBROMOETHANE: &str = include_str!("../tests/data/compound_cid_7839.json");
+
+    #[test]
+    fn computes_aspirin_formula_and_masses() {
+        let aspirin = parse_compounds(ASPIRIN).unwrap().remove(0);
+        let molecule = aspirin.molecule().unwrap();
+        assert_eq!(molecule.formula().to_string(), "C9H8O4");
+        assert!((molecule.average_mass() - 180.159).abs() < 0.001);
+        assert!((molecule.monoisotopic_mass() - 180.04225873).abs() < 1e-6);
+        assert!(aspirin.verify_composition().is_empty());
+    }
+
+    #[test]
+    fn orders_salts_and_charges_like_pubchem() {
+        let salt = parse_compounds(SODIUM_ACETATE).unwrap().remove(0);
+        let molecule = salt.molecule().unwrap();
+        let formula = molecule.formula();
+        assert_eq!(formula.to_string(), "C2H3NaO2");
+        assert_eq!(formula.count("Na"), 1);
+        assert!(salt.verify_composition().is_empty());
+
+        let acetate = &molecule.components()[0];
+        assert_eq!(acetate.formula().to_string(), "C2H3O2-");
+        assert_eq!(molecule.components()[1].formula().to_string(), "Na+");
+    }
+
+    #[test]
+    fn uses_hill_order_without_carbon_and_isotope_masses() {
+        let atoms = AtomInfo {
+            aid: vec![1, 2, 3],
+            element: vec![8, 1, 1],
+            charge: Vec::new(),
+            isotope: vec![AtomInt { aid: 2, value: 2 }, AtomInt { aid: 3, value: 2 }],
+            radical: Vec::new(),
+        };
+        let bonds = BondInfo {
+            aid1: vec![1, 1],
+            aid2: vec![2, 3],
+            order: vec![1, 1],
+        };
+        let heavy_water = Molecule::new(&atoms, Some(&bonds), None);
+        assert_eq!(heavy_water.formula().to_string(), "H2O");
+        assert!((heavy_water.average_mass() - 20.027).abs() < 0.001);
+        assert!((heavy_water.monoisotopic_mass() - 20.02311817).abs() < 1e-6);
+
+        let calcium = AtomInfo {
+            aid: vec![1],
+            element: vec![20],
+            charge: vec![AtomInt { aid: 1, value: 2 }],
+            isotope: Vec::new(),
+            radical: Vec::new(),
+        };
+        assert_eq!(Molecule::new(&calcium, None, None).formula().to_string(), "Ca+2");
+    }
+
+    #[test]
+    fn radicals_take_the_place_of_hydrogens() {
+        let atoms = AtomInfo {
+            aid: vec![1],
+            element: vec![6],
+            charge: Vec::new(),
+            isotope: vec![AtomInt { aid: 1, value: 13 }],
+            radical: vec![AtomRadical { aid: 1, kind: 2 }],
+        };
+        let methyl = Molecule::new(&atoms, None, None);
+        assert_eq!(methyl.formula().to_string(), "CH3");
+        assert!((methyl.monoisotopic_mass() - 16.02683).abs() < 1e-5);
+
+        let carbene = AtomInfo {
+            radical: vec![AtomRadical { aid: 1, kind: 3 }],
+            isotope: Vec::new(),
+            ..atoms
+        };
+        assert_eq!(Molecule::new(&carbene, None, None).formula().to_string(), "CH2");
+    }
+
+    #[test]
+    fn matches_pubchem_for_hydron_and_borohydride() {
+        let hydron = parse_compounds(HYDRON).unwrap().remove(0);
+        assert_eq!(hydron.molecule().unwrap().formula().to_string(), "H+");
+        assert!(hydron.verify_composition().is_empty());
+
+        let borohydride = parse_compounds(SODIUM_BOROHYDRIDE).unwrap().remove(0);
+        assert_eq!(borohydride.molecule().unwrap().formula().to_string(), "BH4Na");
+        assert!(borohydride.verify_composition().is_empty());
+
+        // The same salt as a heavy-atom skeleton: B- must pick up all four hydrogens.
+        let skeleton = AtomInfo {
+            aid: vec![1, 2],
+            element: vec![11, 5],
+            charge: vec![AtomInt { aid: 1, value: 1 }, AtomInt { aid: 2, value: -1 }],
+            isotope: Vec::new(),
+            radical: Vec::new(),
+        };
+        let skeleton = Molecule::new(&skeleton, None, None);
+        assert_eq!(Some(skeleton.formula().to_string()), borohydride.molecular_formula);
+        let weight = borohydride.molecular_weight.unwrap();
+        assert!((skeleton.average_mass() - weight).abs() <= WEIGHT_TOLERANCE);
+        let monoisotopic = borohydride.monoisotopic_mass.unwrap();
+        assert!((skeleton.monoisotopic_mass() - monoisotopic).abs() <= MONOISOTOPIC_MASS_TOLERANCE);
+    }
+
+    #[test]
+    fn compares_monoisotopic_not_exact_mass_for_dibromides() {
+        // Exact mass is that of 79Br81Br, the most likely composition, not of 79Br2.
+        let dibromoethane = parse_compounds(DIBROMOETHANE).unwrap().remove(0);
+        let molecule = dibromoethane.molecule().unwrap();
+        assert_eq!(molecule.formula().to_string(), "C2H4Br2");
+        assert!((molecule.monoisotopic_mass() - 185.86797).abs() < 1e-5);
+        let exact = dibromoethane.exact_mass.unwrap();
+        assert!((exact - molecule.monoisotopic_mass() - 1.998).abs() < 0.001);
+        assert!(dibromoethane.verify_composition().is_empty());
+    }
+
+    #[test]
+    fn reports_disagreeing_pubchem_values() {
+        let mut aspirin = parse_compounds(ASPIRIN).unwrap().remove(0);
+        aspirin.molecular_formula = Some("C9H10O4".to_string());
+        aspirin.molecular_weight = Some(182.17);
+        let discrepancies = aspirin.verify_composition();
+        assert_eq!(discrepancies.len(), 2);
+        assert_eq!(
+            discrepancies[0].to_string(),
+            "Molecular Formula: PubChem reports C9H10O4, computed C9H8O4"
+        );
+        assert_eq!(discrepancies[1].property, "Molecular Weight");
+        assert_eq!(discrepancies[1].computed, "180.16");
+    }
+}
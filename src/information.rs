@@ -0,0 +1,320 @@
+//! Synonym, description and cross-reference lookups (`InformationList` responses).
+
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct InformationListEnvelope<T> {
+    #[serde(rename = "InformationList")]
+    pub list: InformationList<T>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct InformationList<T> {
+    #[serde(rename = "Information", default = "Vec::new")]
+    pub information: Vec<T>,
+}
+
+/// Every synonym PubChem lists for one compound, most common first.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Synonyms {
+    #[serde(rename = "CID")]
+    pub cid: u32,
+    #[serde(rename = "Synonym", default)]
+    pub synonyms: Vec<String>,
+}
+
+impl Synonyms {
+    /// Synonyms that are well-formed CAS Registry Numbers with a valid check digit.
+    pub fn cas_numbers(&self) -> Vec<&str> {
+        self.synonyms
+            .iter()
+            .map(String::as_str)
+            .filter(|synonym| is_cas_number(synonym))
+            .collect()
+    }
+}
+
+/// Checks the `NNNNNNN-NN-N` shape and the CAS check digit.
+pub fn is_cas_number(candidate: &str) -> bool {
+    let parts: Vec<&str> = candidate.split('-').collect();
+    let [first, second, check] = parts[..] else {
+        return false;
+    };
+    let all_digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+    if !(2..=7).contains(&first.len()) || second.len() != 2 || check.len() != 1 {
+        return false;
+    }
+    if !all_digits(first) || !all_digits(second) || !all_digits(check) {
+        return false;
+    }
+    let sum: u32 = first
+        .bytes()
+        .chain(second.bytes())
+        .rev()
+        .enumerate()
+        .map(|(i, b)| (i as u32 + 1) * u32::from(b - b'0'))
+        .sum();
+    sum % 10 == u32::from(check.as_bytes()[0] - b'0')
+}
+
+/// Conformer IDs of a compound's 3D conformer model, as listed by the `conformers` operation.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ConformerIds {
+    #[serde(rename = "CID")]
+    pub cid: u32,
+    #[serde(rename = "ConformerID", default)]
+    pub conformer_ids: Vec<String>,
+}
+
+/// One raw `description` entry: either a title or a sourced description.
+#[derive(Debug, Deserialize)]
+pub(crate) struct DescriptionEntry {
+    #[serde(rename = "CID")]
+    pub cid: u32,
+    #[serde(rename = "Title")]
+    pub title: Option<String>,
+    #[serde(rename = "Description")]
+    pub description: Option<String>,
+    #[serde(rename = "DescriptionSourceName")]
+    pub source_name: Option<String>,
+    #[serde(rename = "DescriptionURL")]
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourcedDescription {
+    pub text: String,
+    pub source_name: Option<String>,
+    pub url: Option<String>,
+}
+
+/// A compound's title and the descriptions contributed by each source.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Description {
+    pub cid: u32,
+    pub title: Option<String>,
+    pub descriptions: Vec<SourcedDescription>,
+}
+
+/// Groups PubChem's flat entry list into one [`Description`] per CID, keeping response order.
+pub(crate) fn group_descriptions(entries: Vec<DescriptionEntry>) -> Vec<Description> {
+    let mut grouped: Vec<Description> = Vec::new();
+    for entry in entries {
+        let index = match grouped.iter().position(|d| d.cid == entry.cid) {
+            Some(index) => index,
+            None => {
+                grouped.push(Description {
+                    cid: entry.cid,
+                    title: None,
+                    descriptions: Vec::new(),
+                });
+                grouped.len() - 1
+            }
+        };
+        let description = &mut grouped[index];
+        if entry.title.is_some() {
+            description.title = entry.title;
+        }
+        if let Some(text) = entry.description {
+            description.descriptions.push(SourcedDescription {
+                text,
+                source_name: entry.source_name,
+                url: entry.url,
+            });
+        }
+    }
+    grouped
+}
+
+/// Cross-reference types accepted by the `xrefs` operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum XrefKind {
+    RegistryID,
+    /// CAS Registry Numbers.
+    RN,
+    PubMedID,
+    MMDBID,
+    ProteinGI,
+    NucleotideGI,
+    TaxonomyID,
+    MIMID,
+    GeneID,
+    ProbeID,
+    PatentID,
+    DBURL,
+    SBURL,
+    SourceName,
+    SourceCategory,
+}
+
+impl XrefKind {
+    pub const ALL: [XrefKind; 15] = [
+        XrefKind::RegistryID,
+        XrefKind::RN,
+        XrefKind::PubMedID,
+        XrefKind::MMDBID,
+        XrefKind::ProteinGI,
+        XrefKind::NucleotideGI,
+        XrefKind::TaxonomyID,
+        XrefKind::MIMID,
+        XrefKind::GeneID,
+        XrefKind::ProbeID,
+        XrefKind::PatentID,
+        XrefKind::DBURL,
+        XrefKind::SBURL,
+        XrefKind::SourceName,
+        XrefKind::SourceCategory,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            XrefKind::RegistryID => "RegistryID",
+            XrefKind::RN => "RN",
+            XrefKind::PubMedID => "PubMedID",
+            XrefKind::MMDBID => "MMDBID",
+            XrefKind::ProteinGI => "ProteinGI",
+            XrefKind::NucleotideGI => "NucleotideGI",
+            XrefKind::TaxonomyID => "TaxonomyID",
+            XrefKind::MIMID => "MIMID",
+            XrefKind::GeneID => "GeneID",
+            XrefKind::ProbeID => "ProbeID",
+            XrefKind::PatentID => "PatentID",
+            XrefKind::DBURL => "DBURL",
+            XrefKind::SBURL => "SBURL",
+            XrefKind::SourceName => "SourceName",
+            XrefKind::SourceCategory => "SourceCategory",
+        }
+    }
+}
+
+impl fmt::Display for XrefKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for XrefKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        XrefKind::ALL
+            .into_iter()
+            .find(|kind| kind.as_str().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| format!("unknown xref type '{}'", s))
+    }
+}
+
+/// Raw `xrefs` entry: the CID plus one array per requested type, holding strings or numbers.
+#[derive(Debug, Deserialize)]
+pub(crate) struct XrefEntry {
+    #[serde(rename = "CID")]
+    pub cid: u32,
+    #[serde(flatten)]
+    pub values: BTreeMap<String, Vec<serde_json::Value>>,
+}
+
+/// Cross-references for one compound, keyed by type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Xrefs {
+    pub cid: u32,
+    pub entries: BTreeMap<XrefKind, Vec<String>>,
+}
+
+impl Xrefs {
+    pub fn get(&self, kind: XrefKind) -> &[String] {
+        self.entries.get(&kind).map(Vec::as_slice).unwrap_or_default()
+    }
+}
+
+impl From<XrefEntry> for Xrefs {
+    fn from(entry: XrefEntry) -> Self {
+        let entries = entry
+            .values
+            .into_iter()
+            .filter_map(|(key, values)| {
+                let kind = key.parse::<XrefKind>().ok()?;
+                let values = values
+                    .into_iter()
+                    .map(|value| match value {
+                        serde_json::Value::String(s) => s,
+                        other => other.to_string(),
+                    })
+                    .collect();
+                Some((kind, values))
+            })
+            .collect();
+        Xrefs {
+            cid: entry.cid,
+            entries,
+        }
+    }
+}
+
+/// How `compound/name` matches a name when listing CIDs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NameType {
+    /// The whole synonym must match (PubChem's default).
+    #[default]
+    Complete,
+    /// Any word within a synonym may match.
+    Word,
+}
+
+impl NameType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NameType::Complete => "complete",
+            NameType::Word => "word",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_valid_cas_numbers_from_synonyms() {
+        let envelope: InformationListEnvelope<Synonyms> =
+            serde_json::from_str(include_str!("../tests/data/synonyms_cid_2244.json")).unwrap();
+        let synonyms = &envelope.list.information[0];
+        assert_eq!(synonyms.cid, 2244);
+        assert_eq!(synonyms.synonyms[0], "aspirin");
+        // 50-78-3 has the CAS shape but a wrong check digit.
+        assert_eq!(synonyms.cas_numbers(), ["50-78-2", "11126-35-5", "11126-37-7"]);
+    }
+
+    #[test]
+    fn validates_cas_check_digit() {
+        assert!(is_cas_number("7732-18-5"));
+        assert!(is_cas_number("58-08-2"));
+        assert!(!is_cas_number("58-08-3"));
+        assert!(!is_cas_number("CHEMBL25"));
+        assert!(!is_cas_number("1-23-4"));
+        assert!(!is_cas_number("12345678-12-3"));
+    }
+
+    #[test]
+    fn groups_title_and_descriptions() {
+        let envelope: InformationListEnvelope<DescriptionEntry> =
+            serde_json::from_str(include_str!("../tests/data/description_cid_2244.json")).unwrap();
+        let descriptions = group_descriptions(envelope.list.information);
+        assert_eq!(descriptions.len(), 1);
+        assert_eq!(descriptions[0].title.as_deref(), Some("Aspirin"));
+        assert_eq!(descriptions[0].descriptions.len(), 2);
+        assert_eq!(descriptions[0].descriptions[1].source_name.as_deref(), Some("LiverTox"));
+    }
+
+    #[test]
+    fn normalises_xref_values_to_strings() {
+        let envelope: InformationListEnvelope<XrefEntry> =
+            serde_json::from_str(include_str!("../tests/data/xrefs_cid_2244.json")).unwrap();
+        let xrefs: Xrefs = envelope.list.information.into_iter().next().unwrap().into();
+        assert_eq!(xrefs.get(XrefKind::RN), ["11126-35-5", "11126-37-7", "50-78-2"]);
+        assert_eq!(xrefs.get(XrefKind::PubMedID)[0], "1234567");
+        assert!(xrefs.get(XrefKind::PatentID).is_empty());
+    }
+}
@@ -0,0 +1,50 @@
+//! Client and data model for the PubChem PUG REST API.
+
+pub mod assay;
+pub mod batch;
+pub mod cache;
+pub mod client;
+pub mod compound;
+pub mod element;
+pub mod error;
+pub mod format;
+pub mod formula;
+pub mod information;
+pub mod listkey;
+pub mod molecule;
+pub mod namespace;
+pub mod plot;
+pub mod properties;
+pub mod retry;
+pub mod search;
+pub mod substance;
+pub mod throttle;
+pub mod view;
+
+pub use assay::{ActivityOutcome, AssaySummary, AssayTarget, Bioactivity};
+pub use batch::BatchSummary;
+pub use cache::{CacheStats, ResponseCache};
+pub use client::{BatchChunk, PubChemClient, DEFAULT_BASE_URL, DEFAULT_CHUNK_SIZE};
+pub use compound::{
+    parse_compounds, AtomInfo, AtomInt, AtomRadical, Bond, BondInfo, Compound, Conformer, Coords, Octahedral, Parity,
+    PentagonalBipyramid, Planar, PropertyValue, Props, Radical, RecordType, SquarePlanar, Stereo, TShape, Tetrahedral,
+    TrigonalBipyramid,
+};
+pub use element::{Element, ELEMENTS};
+pub use error::{Fault, PcPugError, Result};
+pub use format::OutputFormat;
+pub use formula::{Discrepancy, Formula};
+pub use information::{ConformerIds, Description, NameType, SourcedDescription, Synonyms, XrefKind, Xrefs};
+pub use listkey::PollPolicy;
+pub use molecule::{Atom, Molecule};
+pub use namespace::Namespace;
+pub use plot::plot_molecule;
+pub use properties::{CompoundProperties, Property};
+pub use retry::RetryPolicy;
+pub use search::{SearchKind, SearchOptions};
+pub use substance::Substance;
+pub use throttle::{RateLimit, ThrottlingStatus};
+pub use view::{
+    ExperimentalProperties, ExperimentalValue, GhsClassification, HazardStatement, Pictogram, ViewRecord, ViewSection,
+    DEFAULT_VIEW_BASE_URL,
+};
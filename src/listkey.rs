@@ -0,0 +1,93 @@
+//! Asynchronous PUG REST operations that answer with a ListKey to poll.
+//!
+//! Formula lookups, structure searches and large batches return
+//! `{"Waiting": {"ListKey": "...", "Message": "..."}}` until the result is ready.
+
+use serde::Deserialize;
+use std::time::Duration;
+
+/// How long and how often [`PubChemClient`](crate::PubChemClient) polls a ListKey.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PollPolicy {
+    /// Delay before the first poll; doubles after each further `Waiting` answer.
+    pub initial_interval: Duration,
+    /// Upper bound on the delay between polls.
+    pub max_interval: Duration,
+    /// Give up once the result hasn't arrived after this long.
+    pub deadline: Duration,
+}
+
+impl Default for PollPolicy {
+    fn default() -> Self {
+        PollPolicy {
+            initial_interval: Duration::from_secs(1),
+            max_interval: Duration::from_secs(10),
+            deadline: Duration::from_secs(120),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Waiting {
+    #[serde(rename = "ListKey")]
+    pub list_key: String,
+    #[serde(rename = "Message", default)]
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WaitingEnvelope {
+    #[serde(rename = "Waiting")]
+    waiting: Waiting,
+}
+
+impl Waiting {
+    /// Returns the pending operation if `body` is a `Waiting` response.
+    pub fn from_body(body: &str) -> Option<Self> {
+        // Cheap check first: full records can be megabytes.
+        if !body.trim_start().starts_with('{') || !body.contains("\"Waiting\"") {
+            return None;
+        }
+        serde_json::from_str::<WaitingEnvelope>(body)
+            .ok()
+            .map(|envelope| envelope.waiting)
+    }
+}
+
+/// `{"IdentifierList": {"CID": [...]}}`, the answer to `.../cids/JSON` requests.
+#[derive(Debug, Deserialize)]
+pub struct IdentifierListEnvelope {
+    #[serde(rename = "IdentifierList")]
+    pub identifier_list: IdentifierList,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct IdentifierList {
+    #[serde(rename = "CID", default)]
+    pub cids: Vec<u32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognises_waiting_response() {
+        let body = include_str!("../tests/data/waiting.json");
+        let waiting = Waiting::from_body(body).unwrap();
+        assert_eq!(waiting.list_key, "2338361557384542337");
+        assert_eq!(waiting.message.as_deref(), Some("Your request is running"));
+    }
+
+    #[test]
+    fn ignores_other_bodies() {
+        assert!(Waiting::from_body(include_str!("../tests/data/compound_cid_2244.json")).is_none());
+        assert!(Waiting::from_body("Waiting").is_none());
+    }
+
+    #[test]
+    fn parses_identifier_list() {
+        let envelope: IdentifierListEnvelope = serde_json::from_str(include_str!("../tests/data/cids_formula_c9h8o4.json")).unwrap();
+        assert_eq!(envelope.identifier_list.cids[..3], [2244, 54670067, 10745]);
+    }
+}
@@ -1,280 +1,658 @@
-//use reqwest::Error;
-use serde::Deserialize;
-use plotters::prelude::*;
-use std::env;
-use std::fmt;
+use clap::error::ErrorKind;
+use clap::{Args, CommandFactory, Parser, Subcommand};
+use futures::stream::{self, StreamExt};
+use pcpug::{
+    plot_molecule, ActivityOutcome, BatchChunk, BatchSummary, ResponseCache, Compound, Fault, NameType, Namespace, OutputFormat, PcPugError, Property, PubChemClient, RecordType,
+    SearchKind, SearchOptions, XrefKind,
+};
+use std::error::Error;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Duration;
 
-const BASEURL: &str = "https://pubchem.ncbi.nlm.nih.gov/rest/pug/compound/name/";
-const RETURNTYPE: &str = "/JSON";
 const PNGDIR: &str = "png_out/";
+const DOWNLOADDIR: &str = "downloads/";
+const CACHEDIR: &str = "pcpug_cache/";
 
-#[derive(Debug, Deserialize)]
-struct AtomInfo {
-    aid: Vec<u32>,
-    element: Vec<u32>,
+/// Look up compounds on PubChem, print their records and plot their 2D structures.
+#[derive(Debug, Parser)]
+#[command(subcommand_negates_reqs = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[command(flatten)]
+    lookup: LookupArgs,
+    #[command(flatten)]
+    cache: CacheArgs,
 }
 
-#[derive(Debug, Deserialize)]
-struct BondInfo {
-    aid1: Vec<u32>,
-    aid2: Vec<u32>,
-    order: Vec<u32>,
+#[derive(Debug, Args)]
+struct CacheArgs {
+    /// Directory for cached PubChem responses
+    #[arg(long, global = true, default_value = CACHEDIR)]
+    cache_dir: PathBuf,
+    /// Seconds a cached response is reused before it is fetched again (0 keeps entries forever)
+    #[arg(long, global = true, default_value_t = 86400)]
+    cache_ttl: u64,
+    /// Always query PubChem and don't store responses
+    #[arg(long, global = true, conflicts_with = "offline")]
+    no_cache: bool,
+    /// Answer only from the cache; requests it has no entry for fail
+    #[arg(long, global = true)]
+    offline: bool,
 }
 
-#[derive(Debug, Deserialize)]
-struct Conformer {
-    x: Vec<f64>,
-    y: Vec<f64>,
+impl CacheArgs {
+    fn response_cache(&self) -> ResponseCache {
+        let ttl = (self.cache_ttl > 0).then(|| Duration::from_secs(self.cache_ttl));
+        ResponseCache::new(&self.cache_dir).ttl(ttl).offline(self.offline)
+    }
 }
 
-#[derive(Debug, Deserialize)]
-struct Coords {
-    conformers: Vec<Conformer>,
+#[derive(Debug, Args)]
+struct LookupArgs {
+    /// How to interpret the identifiers: name, cid, smiles, inchi, inchikey, formula or listkey
+    #[arg(short, long, default_value = "name")]
+    namespace: Namespace,
+    /// Stop at the first identifier that fails and exit non-zero
+    #[arg(long)]
+    fail_fast: bool,
+    /// Number of lookups to run concurrently (still subject to PubChem's rate limit)
+    #[arg(short, long, default_value_t = 1)]
+    jobs: usize,
+    /// Fetch cid or inchikey identifiers as comma-separated list requests of this many records each (other namespaces are rejected)
+    #[arg(long)]
+    chunk_size: Option<usize>,
+    /// Request 3D records (conformer coordinates, energy, shape volume) instead of 2D depictions
+    #[arg(long = "3d")]
+    three_d: bool,
+    /// Print only these comma-separated property table columns (e.g. MolecularWeight,XLogP,TPSA) instead of full records
+    #[arg(long, value_delimiter = ',')]
+    properties: Vec<Property>,
+    /// Compound identifiers to look up
+    #[arg(required = true)]
+    identifiers: Vec<String>,
 }
 
-#[derive(Debug, Deserialize)]
-struct Props {
-    urn: Urn,
-    value: PropertyValue,
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Find compounds that contain the query structure
+    Substructure(SearchArgs),
+    /// Find compounds contained in the query structure
+    Superstructure(SearchArgs),
+    /// Find compounds with a similar 2D fingerprint
+    Similarity {
+        /// Minimum Tanimoto similarity, in percent
+        #[arg(long, default_value_t = 90)]
+        threshold: u8,
+        #[command(flatten)]
+        search: SearchArgs,
+    },
+    /// Find compounds identical to the query structure
+    Identity(SearchArgs),
+    /// Print every synonym, plus the CAS Registry Numbers among them
+    Synonyms(InfoArgs),
+    /// Print the title and sourced descriptions
+    Description(InfoArgs),
+    /// Print cross-references to other databases
+    Xrefs {
+        /// Comma-separated xref types, e.g. RN,PubMedID,PatentID
+        #[arg(long, value_delimiter = ',', default_value = "RN,RegistryID")]
+        types: Vec<XrefKind>,
+        #[command(flatten)]
+        info: InfoArgs,
+    },
+    /// List the conformer IDs of each compound's 3D conformer model
+    Conformers(InfoArgs),
+    /// Print GHS hazard classification and experimental properties from PUG View
+    Safety(InfoArgs),
+    /// Save raw PubChem output (SDF, XML, PNG, ...) to disk without re-encoding
+    Download {
+        /// How to interpret the identifiers: name, cid, smiles, inchi, inchikey, formula or listkey
+        #[arg(short, long, default_value = "name")]
+        namespace: Namespace,
+        /// Output format: json, xml, sdf, csv, txt, png or asnt
+        #[arg(short, long, default_value = "sdf")]
+        format: OutputFormat,
+        /// Operation to render instead of the full record, e.g. cids, synonyms or property/MolecularWeight,XLogP
+        #[arg(long)]
+        operation: Option<String>,
+        /// Request 3D records
+        #[arg(long = "3d")]
+        three_d: bool,
+        /// Directory to save files into
+        #[arg(short, long, default_value = DOWNLOADDIR)]
+        out_dir: PathBuf,
+        /// Compound identifiers to download
+        #[arg(required = true)]
+        identifiers: Vec<String>,
+    },
+    /// Inspect or clean the response cache
+    Cache {
+        #[command(subcommand)]
+        action: CacheCommand,
+    },
+    /// List CIDs matching each name
+    Cids {
+        /// Match any word within a synonym instead of the complete synonym
+        #[arg(long)]
+        word: bool,
+        /// Stop at the first name that fails and exit non-zero
+        #[arg(long)]
+        fail_fast: bool,
+        /// Compound names
+        #[arg(required = true)]
+        names: Vec<String>,
+    },
+    /// Print depositor substance records and the CIDs they standardize to
+    Substance {
+        /// Substance IDs
+        #[arg(required = true, value_delimiter = ',')]
+        sids: Vec<u32>,
+    },
+    /// Print BioAssay summaries: targets, method and active/inactive counts
+    Assay {
+        /// BioAssay IDs
+        #[arg(required = true, value_delimiter = ',')]
+        aids: Vec<u32>,
+    },
+    /// Print every assay outcome recorded for each compound as TSV
+    Bioactivity {
+        /// How to interpret the identifiers: name, cid, smiles, inchi, inchikey, formula or listkey
+        #[arg(short, long, default_value = "name")]
+        namespace: Namespace,
+        /// Only print rows whose outcome is Active
+        #[arg(long)]
+        active: bool,
+        /// Stop at the first identifier that fails and exit non-zero
+        #[arg(long)]
+        fail_fast: bool,
+        /// Compound identifiers to look up
+        #[arg(required = true)]
+        identifiers: Vec<String>,
+    },
 }
 
-#[derive(Debug, Deserialize)]
-struct Stereo {
-    tetrahedral: Option<Tetrahedral>,
+#[derive(Debug, Subcommand)]
+enum CacheCommand {
+    /// Print the number of entries, expired entries and bytes on disk
+    Stats,
+    /// Delete expired entries
+    Prune {
+        /// Delete every entry, not only expired ones
+        #[arg(long)]
+        all: bool,
+    },
 }
 
-#[derive(Debug, Deserialize)]
-struct Tetrahedral {
-    above: u32,
-    below: u32,
-    bottom: u32,
-    center: u32,
-    parity: u32,
-    top: u32,
-    #[serde(rename = "type")]
-    ttype: u32,
+#[derive(Debug, Args)]
+struct InfoArgs {
+    /// How to interpret the identifiers: name, cid, smiles, inchi, inchikey, formula or listkey
+    #[arg(short, long, default_value = "name")]
+    namespace: Namespace,
+    /// Also print the full compound record
+    #[arg(long)]
+    record: bool,
+    /// Compound identifiers to look up
+    #[arg(required = true)]
+    identifiers: Vec<String>,
 }
 
-#[derive(Debug, Deserialize)]
-struct Urn {
-    datatype: Option<u32>,
-    label: Option<String>,
-    name: Option<String>,
-    release: Option<String>,
-    software: Option<String>,
-    source: Option<String>,
-    version: Option<String>,
+#[derive(Debug, Args)]
+struct SearchArgs {
+    /// Query structure
+    query: String,
+    /// How to interpret the query: smiles, inchi or cid
+    #[arg(short, long, default_value = "smiles")]
+    namespace: Namespace,
+    /// Stop after this many hits
+    #[arg(long)]
+    max_records: Option<u32>,
+    /// Fetch and print the full record for every hit instead of only its CID
+    #[arg(long)]
+    records: bool,
 }
 
-#[derive(Debug, Deserialize)]
-struct PropertyValue {
-    fval: Option<f64>,
-    ival: Option<u32>,
-    sval: Option<String>,
-    binary: Option<String>,
+/// Turns an identifier into a safe file stem; SMILES and InChI contain `/` and other path characters.
+fn file_stem(identifier: &str) -> String {
+    identifier
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect()
 }
 
-#[derive(Debug, Deserialize)]
-struct IUPACName {
-    #[serde(rename = "IUPACName")]
-    name: Option<String>,
+/// Prints a record, warning on stderr about any PubChem descriptor that its atoms don't reproduce.
+fn print_compound(identifier: &str, compound: &Compound) {
+    println!("{}", compound);
+    for discrepancy in compound.verify_composition() {
+        eprintln!("{} :: WARNING -> {}", identifier, discrepancy);
+    }
 }
 
-#[derive(Debug, Deserialize)]
-struct Compound {
-    cid: Option<u32>,
-    atoms: Option<AtomInfo>,
-    bonds: Option<Vec<BondInfo>>,
-    coords: Option<Vec<Coords>>,
-    props: Option<Vec<Props>>,
-    stereo: Option<Vec<Stereo>>,
-    molecular_formula: Option<String>,
-    molecular_weight: Option<f64>,
-    inchi: Option<String>,
-    inchikey: Option<String>,
-    isomeric_smiles: Option<String>,
-    tpsa: Option<f64>,
-    xlogp: Option<f64>,
-    exact_mass: Option<f64>,
-    complexity: Option<f64>,
-    h_bond_donor_count: Option<u32>,
-    h_bond_acceptor_count: Option<u32>,
-    rotatable_bond_count: Option<u32>,
-    heavy_atom_count: Option<u32>,
-    charge: Option<i32>,
-    iupac: Option<Vec<IUPACName>>,
+/// `<identifier>_<cid>.png`, so several records returned for one identifier don't overwrite each other.
+fn png_name(identifier: &str, cid: Option<u32>) -> String {
+    match cid {
+        Some(cid) => format!("{}_{}.png", file_stem(identifier), cid),
+        None => format!("{}.png", file_stem(identifier)),
+    }
 }
 
-impl fmt::Display for Compound {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        writeln!(f, "Compound Information:")?;
-        writeln!(f, "---------------------")?;
-        writeln!(f, "CID: {:?}", self.cid)?;
-        writeln!(f, "Molecular Formula: {:?}", self.molecular_formula)?;
-        writeln!(f, "Molecular Weight: {:?}", self.molecular_weight)?;
-        writeln!(f, "InChI: {:?}", self.inchi)?;
-        writeln!(f, "InChIKey: {:?}", self.inchikey)?;
-        writeln!(f, "Isomeric SMILES: {:?}", self.isomeric_smiles)?;
-        writeln!(f, "TPSA: {:?}", self.tpsa)?;
-        writeln!(f, "XLogP: {:?}", self.xlogp)?;
-        writeln!(f, "Exact Mass: {:?}", self.exact_mass)?;
-        writeln!(f, "Complexity: {:?}", self.complexity)?;
-        writeln!(f, "H-Bond Donor Count: {:?}", self.h_bond_donor_count)?;
-        writeln!(f, "H-Bond Acceptor Count: {:?}", self.h_bond_acceptor_count)?;
-        writeln!(f, "Rotatable Bond Count: {:?}", self.rotatable_bond_count)?;
-        writeln!(f, "Heavy Atom Count: {:?}", self.heavy_atom_count)?;
-        writeln!(f, "Charge: {:?}", self.charge)?;
-        writeln!(f, "---------------------")?;
-        if let Some(atoms) = &self.atoms {
-            writeln!(f, "Atoms:")?;
-            for (i, element) in atoms.element.iter().enumerate() {
-                writeln!(f, "  Atom ID: {}, Element: {}", atoms.aid[i], element)?;
+fn report(identifier: &str, compounds: Vec<Compound>, png_dir: &Path) -> Result<(), Box<dyn Error>> {
+    for compound in compounds {
+        print_compound(identifier, &compound);
+        if let (Some(atoms), Some(coords), Some(bonds)) = (&compound.atoms, &compound.coords, &compound.bonds) {
+            let png_path = png_dir.join(png_name(identifier, compound.cid));
+            let skipped = plot_molecule(&png_path, identifier, atoms, coords, bonds)?;
+            if skipped > 0 {
+                eprintln!("{} :: WARNING -> {} bond(s) left out of the plot: atom has no coordinates", identifier, skipped);
             }
-        } else {
-            writeln!(f, "Atoms: None")?;
         }
-        writeln!(f, "---------------------")?;
-        if let Some(bonds) = &self.bonds {
-            writeln!(f, "Bonds:")?;
-            for i in 0..bonds.aid1.len() {
-                writeln!(
-                    f,
-                    "  Bond {}: Atom1 {}, Atom2 {}, Order {}",
-                    i + 1,
-                    bonds.aid1[i],
-                    bonds.aid2[i],
-                    bonds.order[i]
-                )?;
+    }
+    Ok(())
+}
+
+async fn run_search(client: &PubChemClient, kind: SearchKind, args: SearchArgs) -> ExitCode {
+    let options = SearchOptions {
+        max_records: args.max_records,
+    };
+    let result = if args.records {
+        client
+            .search_compounds(kind, args.namespace, &args.query, options)
+            .await
+            .map(|compounds| compounds.iter().for_each(|compound| print_compound(&args.query, compound)))
+    } else {
+        client
+            .search(kind, args.namespace, &args.query, options)
+            .await
+            .map(|cids| cids.iter().for_each(|cid| println!("{}", cid)))
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{} :: ERROR -> {}", args.query, err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// What an information subcommand prints for each identifier.
+enum Info {
+    Synonyms,
+    Description,
+    Xrefs(Vec<XrefKind>),
+    Safety,
+    Conformers,
+}
+
+async fn print_info(client: &PubChemClient, info: &Info, namespace: Namespace, identifier: &str) -> Result<(), PcPugError> {
+    match info {
+        Info::Synonyms => {
+            for entry in client.get_synonyms(namespace, identifier).await? {
+                println!("CID {} Synonyms:", entry.cid);
+                for synonym in &entry.synonyms {
+                    println!("  {}", synonym);
+                }
+                println!("CAS Numbers: {}", entry.cas_numbers().join(", "));
             }
-        } else {
-            writeln!(f, "Bonds: None")?;
         }
-        writeln!(f, "---------------------")?;
-        if let Some(coords) = &self.coords {
-            writeln!(f, "Coordinates:")?;
-            for (i, conformer) in coords.iter().flat_map(|c| c.conformers.iter()).enumerate() {
-                writeln!(
-                    f,
-                    "  Conformer {}:\\nX = {:?},\nY = {:?}",
-                    i + 1,
-                    conformer.x,
-                    conformer.y
-                )?;
+        Info::Description => {
+            for entry in client.get_description(namespace, identifier).await? {
+                println!("CID {}: {}", entry.cid, entry.title.as_deref().unwrap_or("Untitled"));
+                for description in &entry.descriptions {
+                    let source = description.source_name.as_deref().unwrap_or("Unknown source");
+                    println!("  [{}] {}", source, description.text);
+                }
             }
-        } else {
-            writeln!(f, "Coordinates: None")?;
         }
-        writeln!(f, "---------------------")?;
-        if let Some(props) = &self.props {
-            writeln!(f, "Properties:")?;
-            for prop in props {
-                writeln!(
-                    f,
-                    "  Property: {:?}, Value: {:?}",
-                    prop.urn.label, prop.value
-                )?;
+        Info::Xrefs(kinds) => {
+            for entry in client.get_xrefs(namespace, identifier, kinds).await? {
+                println!("CID {} Cross-references:", entry.cid);
+                for kind in kinds {
+                    println!("  {}: {}", kind, entry.get(*kind).join(", "));
+                }
             }
-        } else {
-            writeln!(f, "Properties: None")?;
         }
-        writeln!(f, "---------------------")?;
-        if let Some(stereo) = &self.stereo {
-            writeln!(f, "Stereo Information:")?;
-            for (i, s) in stereo.iter().enumerate() {
-                if let Some(tetrahedral) = &s.tetrahedral {
-                    writeln!(
-                        f,
-                        "  Stereo {}: Above = {}, Below = {}, Bottom = {}, Center = {}, Parity = {}, Top = {}, Type = {}",
-                        i + 1,
-                        tetrahedral.above,
-                        tetrahedral.below,
-                        tetrahedral.bottom,
-                        tetrahedral.center,
-                        tetrahedral.parity,
-                        tetrahedral.top,
-                        tetrahedral.ttype
-                    )?;
+        Info::Conformers => {
+            for entry in client.get_conformer_ids(namespace, identifier).await? {
+                println!("CID {} Conformers:", entry.cid);
+                for id in &entry.conformer_ids {
+                    println!("  {}", id);
                 }
             }
-        } else {
-            writeln!(f, "Stereo Information: None")?;
         }
-        writeln!(f, "---------------------")?;
-        if let Some(iupac) = &self.iupac {
-            writeln!(f, "IUPAC Names:")?;
-            for name in iupac {
-                writeln!(f, "  {}", name.name.as_deref().unwrap_or("Unknown"))?;
+        Info::Safety => {
+            for cid in client.get_cids(namespace, identifier).await? {
+                println!("CID {}", cid);
+                println!("{}", client.get_ghs_classification(cid).await?);
+                println!("{}", client.get_experimental_properties(cid).await?);
             }
-        } else {
-            writeln!(f, "IUPAC Names: None")?;
         }
-        Ok(())
+    }
+    Ok(())
+}
+
+async fn run_info(client: &PubChemClient, info: Info, args: InfoArgs) -> ExitCode {
+    let mut summary = BatchSummary::new();
+    for identifier in &args.identifiers {
+        let mut result = print_info(client, &info, args.namespace, identifier).await;
+        if args.record && result.is_ok() {
+            result = client
+                .get_compounds(args.namespace, identifier)
+                .await
+                .map(|compounds| compounds.iter().for_each(|compound| print_compound(identifier, compound)));
+        }
+        let result = result.map_err(Box::<dyn Error>::from);
+        if let Err(err) = &result {
+            eprintln!("{} :: ERROR -> {}", identifier, err);
+        }
+        summary.record(identifier, &result);
+    }
+    if summary.all_failed() {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+async fn run_download(
+    client: &PubChemClient,
+    namespace: Namespace,
+    format: OutputFormat,
+    operation: Option<&str>,
+    out_dir: &Path,
+    identifiers: &[String],
+) -> ExitCode {
+    if let Err(err) = fs::create_dir_all(out_dir) {
+        eprintln!("main() :: ERROR -> could not create {}: {}", out_dir.display(), err);
+        return ExitCode::FAILURE;
+    }
+    let mut summary = BatchSummary::new();
+    for identifier in identifiers {
+        let path = out_dir.join(format!("{}.{}", file_stem(identifier), format.extension()));
+        let result = match client.download(namespace, identifier, operation, format).await {
+            Ok(body) => fs::write(&path, body).map_err(Box::<dyn Error>::from),
+            Err(err) => Err(Box::<dyn Error>::from(err)),
+        };
+        match &result {
+            Ok(()) => println!("{}", path.display()),
+            Err(err) => eprintln!("{} :: ERROR -> {}", identifier, err),
+        }
+        summary.record(identifier, &result);
+    }
+    eprintln!("{}", summary);
+    if summary.all_failed() {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
     }
 }
 
-fn plot_molecule(name: &str, coords: &[Coords], bonds: &[BondInfo]) -> Result<(), Box<dyn std::error::Error>> {
-    let png_name = format!("{}{}.png", PNGDIR, name);
-    let root = BitMapBackend::new(&png_name, (800, 600)).into_drawing_area();
-    root.fill(&WHITE)?;
-    let mut chart = ChartBuilder::on(&root)
-        .caption(name, ("sans-serif", 50).into_font())
-        .margin(20)
-        .x_label_area_size(40)
-        .y_label_area_size(40)
-        .build_cartesian_2d(0f64..10f64, 0f64..10f64)?;
-    chart.configure_mesh().draw()?;
-    if let Some(conformer) = coords.get(0).and_then(|c| c.conformers.get(0)) {
-        for bond in bonds {
-            for i in 0..bond.aid1.len() {
-                let atom1_idx = bond.aid1[i] as usize;
-                let atom2_idx = bond.aid2[i] as usize;
-                chart.draw_series(LineSeries::new(
-                    vec![
-                        (conformer.x[atom1_idx], conformer.y[atom1_idx]),
-                        (conformer.x[atom2_idx], conformer.y[atom2_idx]),
-                    ],
-                    &RED,
-                ))?;
+fn run_cache(cache: &ResponseCache, action: CacheCommand) -> ExitCode {
+    let result = match action {
+        CacheCommand::Stats => cache.stats().map(|stats| println!("{}: {}", cache.dir().display(), stats)),
+        CacheCommand::Prune { all } => cache
+            .prune(all)
+            .map(|removed| println!("Removed {} entries from {}", removed, cache.dir().display())),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("main() :: ERROR -> cache {}: {}", cache.dir().display(), err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run_cids(client: &PubChemClient, names: &[String], word: bool, fail_fast: bool) -> ExitCode {
+    let name_type = if word { NameType::Word } else { NameType::Complete };
+    let mut summary = BatchSummary::new();
+    for name in names {
+        let result = client.get_cids_by_name(name, name_type).await.map_err(Box::<dyn Error>::from);
+        match &result {
+            Ok(cids) => {
+                let cids: Vec<String> = cids.iter().map(u32::to_string).collect();
+                println!("{}\t{}", name, cids.join(","));
             }
+            Err(err) => eprintln!("{} :: ERROR -> {}", name, err),
+        }
+        summary.record(name, &result);
+        if fail_fast && result.is_err() {
+            break;
         }
+    }
+    eprintln!("{}", summary);
+    if summary.all_failed() || (fail_fast && summary.has_failures()) {
+        ExitCode::FAILURE
     } else {
-        eprintln!("No conformers available for plotting.");
+        ExitCode::SUCCESS
     }
-    
-    Ok(())
 }
 
-#[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let path = Path::new(PNGDIR);
-    if !path.exists() {
-        fs::create_dir_all(path)?;
+async fn run_substances(client: &PubChemClient, sids: &[u32]) -> ExitCode {
+    match client.get_substances(sids).await {
+        Ok(substances) => {
+            substances.iter().for_each(|substance| println!("{}", substance));
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("main() :: ERROR -> substance lookup failed: {}", err);
+            ExitCode::FAILURE
+        }
     }
-    let args: Vec<String> = env::args().collect();
-    if args.len() < 2 {
-        println!("src/main.rs usage :: cargo run <compound_name> <compound_name> <compound_name> ... (at least one additional argument)");
-        return Ok(());
+}
+
+async fn run_assays(client: &PubChemClient, aids: &[u32]) -> ExitCode {
+    match client.get_assay_summaries(aids).await {
+        Ok(summaries) => {
+            summaries.iter().for_each(|summary| println!("{}", summary));
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("main() :: ERROR -> assay lookup failed: {}", err);
+            ExitCode::FAILURE
+        }
     }
-    let names: Vec<String> = args.into_iter()
-        .skip(1)
-        .filter(|arg| !arg.chars().all(char::is_numeric))
-        .collect();
-    if names.is_empty() {
-        eprintln!("\nmain() :: ERROR -> None of the compounds entered are purely alphabetical");
-        return Ok(());
+}
+
+async fn run_bioactivity(
+    client: &PubChemClient,
+    namespace: Namespace,
+    identifiers: &[String],
+    active: bool,
+    fail_fast: bool,
+) -> ExitCode {
+    let mut summary = BatchSummary::new();
+    println!("Query\tAID\tCID\tOutcome\tTarget\tActivity\tValue [uM]\tAssay");
+    for identifier in identifiers {
+        let result = client.get_bioactivities(namespace, identifier).await.map_err(Box::<dyn Error>::from);
+        match &result {
+            Ok(rows) => {
+                for row in rows.iter().filter(|row| !active || row.outcome == ActivityOutcome::Active) {
+                    println!(
+                        "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+                        identifier,
+                        row.aid,
+                        row.cid.map(|cid| cid.to_string()).unwrap_or_default(),
+                        row.outcome,
+                        row.target_accession.as_deref().unwrap_or_default(),
+                        row.activity_name.as_deref().unwrap_or_default(),
+                        row.activity_value.map(|value| value.to_string()).unwrap_or_default(),
+                        row.assay_name.as_deref().unwrap_or_default(),
+                    );
+                }
+            }
+            Err(err) => eprintln!("{} :: ERROR -> {}", identifier, err),
+        }
+        summary.record(identifier, &result);
+        if fail_fast && result.is_err() {
+            break;
+        }
     }
-    for name in &names {
-        let url = format!("{}{}{}", BASEURL, name, RETURNTYPE);
-        let response = reqwest::get(&url).await?;
-        let compound: Compound = response.json().await?;
-        println!("{}", compound);
-        if let Some(coords) = compound.coords {
-            if let Some(bonds) = compound.bonds {
-                plot_molecule(name, &coords, &bonds)?;
+    eprintln!("{}", summary);
+    if summary.all_failed() || (fail_fast && summary.has_failures()) {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+async fn run_properties(client: &PubChemClient, cli: &LookupArgs) -> ExitCode {
+    match client.get_properties(cli.namespace, &cli.identifiers, &cli.properties).await {
+        Ok(rows) => {
+            let header: Vec<&str> = cli.properties.iter().map(Property::as_str).collect();
+            println!("CID\t{}", header.join("\t"));
+            for row in &rows {
+                let values: Vec<String> = cli
+                    .properties
+                    .iter()
+                    .map(|&property| row.value(property).unwrap_or_default())
+                    .collect();
+                println!("{}\t{}", row.cid, values.join("\t"));
             }
+            if rows.is_empty() {
+                ExitCode::FAILURE
+            } else {
+                ExitCode::SUCCESS
+            }
+        }
+        Err(err) => {
+            eprintln!("main() :: ERROR -> property lookup failed: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Records fetched for one identifier, or why the lookup failed.
+type Fetched = Result<Vec<Compound>, Box<dyn Error>>;
+
+/// Per-identifier results of one list request; a failed request fails each of its identifiers.
+fn chunk_results(chunk: BatchChunk) -> Vec<(String, Fetched)> {
+    match chunk.compounds {
+        Ok(compounds) => chunk
+            .identifiers
+            .into_iter()
+            .zip(compounds)
+            .map(|(identifier, compound)| {
+                let result = compound.map(|compound| vec![compound]).ok_or_else(|| {
+                    let fault = Fault::new("PUGREST.NotFound", "No record returned for this identifier");
+                    Box::<dyn Error>::from(PcPugError::NotFound(fault))
+                });
+                (identifier, result)
+            })
+            .collect(),
+        Err(err) => {
+            let message = format!("list request failed: {}", err);
+            chunk
+                .identifiers
+                .into_iter()
+                .map(|identifier| (identifier, Err(Box::<dyn Error>::from(message.clone()))))
+                .collect()
+        }
+    }
+}
+
+async fn run_lookup(client: &PubChemClient, cli: LookupArgs) -> ExitCode {
+    if !cli.properties.is_empty() {
+        return run_properties(client, &cli).await;
+    }
+    let png_dir = Path::new(PNGDIR);
+    if let Err(err) = fs::create_dir_all(png_dir) {
+        eprintln!("main() :: ERROR -> could not create {}: {}", PNGDIR, err);
+        return ExitCode::FAILURE;
+    }
+    let mut summary = BatchSummary::new();
+    let mut results = match cli.chunk_size {
+        Some(chunk_size) => match client.get_compounds_batch(cli.namespace, &cli.identifiers, chunk_size).await {
+            Ok(chunks) => stream::iter(chunks.into_iter().flat_map(chunk_results)).boxed_local(),
+            Err(err) => {
+                eprintln!("main() :: ERROR -> list request failed: {}", err);
+                for identifier in &cli.identifiers {
+                    summary.record_error(identifier, &err);
+                }
+                eprintln!("{}", summary);
+                return ExitCode::FAILURE;
+            }
+        },
+        None => client
+            .fetch_many(cli.namespace, cli.identifiers.clone(), cli.jobs)
+            .map(|(identifier, fetched)| (identifier, fetched.map_err(Box::<dyn Error>::from)))
+            .boxed_local(),
+    };
+    while let Some((identifier, fetched)) = results.next().await {
+        let result = fetched.and_then(|compounds| report(&identifier, compounds, png_dir));
+        if let Err(err) = &result {
+            eprintln!("{} :: ERROR -> {}", identifier, err);
+        }
+        summary.record(&identifier, &result);
+        if cli.fail_fast && result.is_err() {
+            break;
+        }
+    }
+    eprintln!("{}", summary);
+    if summary.all_failed() || (cli.fail_fast && summary.has_failures()) {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let client = if cli.cache.no_cache {
+        PubChemClient::new()
+    } else {
+        PubChemClient::new().cache(cli.cache.response_cache())
+    };
+    match cli.command {
+        Some(Command::Substructure(args)) => run_search(&client, SearchKind::Substructure, args).await,
+        Some(Command::Superstructure(args)) => run_search(&client, SearchKind::Superstructure, args).await,
+        Some(Command::Similarity { threshold, search }) => {
+            run_search(&client, SearchKind::Similarity { threshold }, search).await
+        }
+        Some(Command::Identity(args)) => run_search(&client, SearchKind::Identity, args).await,
+        Some(Command::Synonyms(args)) => run_info(&client, Info::Synonyms, args).await,
+        Some(Command::Description(args)) => run_info(&client, Info::Description, args).await,
+        Some(Command::Xrefs { types, info }) => run_info(&client, Info::Xrefs(types), info).await,
+        Some(Command::Conformers(args)) => run_info(&client, Info::Conformers, args).await,
+        Some(Command::Safety(args)) => run_info(&client, Info::Safety, args).await,
+        Some(Command::Download {
+            namespace,
+            format,
+            operation,
+            three_d,
+            out_dir,
+            identifiers,
+        }) => {
+            let client = if three_d { client.record_type(RecordType::ThreeD) } else { client };
+            run_download(&client, namespace, format, operation.as_deref(), &out_dir, &identifiers).await
+        }
+        Some(Command::Cache { action }) => run_cache(&cli.cache.response_cache(), action),
+        Some(Command::Cids { word, fail_fast, names }) => run_cids(&client, &names, word, fail_fast).await,
+        Some(Command::Substance { sids }) => run_substances(&client, &sids).await,
+        Some(Command::Assay { aids }) => run_assays(&client, &aids).await,
+        Some(Command::Bioactivity {
+            namespace,
+            active,
+            fail_fast,
+            identifiers,
+        }) => run_bioactivity(&client, namespace, &identifiers, active, fail_fast).await,
+        None => {
+            if cli.lookup.chunk_size.is_some() && !cli.lookup.namespace.supports_list_input() {
+                Cli::command()
+                    .error(
+                        ErrorKind::ArgumentConflict,
+                        format!("--chunk-size needs a cid or inchikey namespace, not {}", cli.lookup.namespace),
+                    )
+                    .exit();
+            }
+            let client = if cli.lookup.three_d {
+                client.record_type(RecordType::ThreeD)
+            } else {
+                client
+            };
+            run_lookup(&client, cli.lookup).await
         }
     }
-    Ok(())
 }
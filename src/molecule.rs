@@ -0,0 +1,403 @@
+//! Molecular graph over a compound's atoms and bonds.
+
+use crate::compound::{AtomInfo, Bond, BondInfo, Compound, Coords, Radical, Stereo};
+use crate::element::Element;
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+/// One atom of a [`Molecule`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Atom {
+    pub aid: u32,
+    pub atomic_number: u32,
+    /// `None` for PubChem pseudo-elements (R groups, lone pairs, ...).
+    pub element: Option<&'static Element>,
+    pub charge: i32,
+    /// Mass number when the atom is a specific isotope.
+    pub isotope: Option<u32>,
+    pub radical: Option<Radical>,
+    /// Position in the first conformer; `z` is 0 for 2D records.
+    pub position: Option<[f64; 3]>,
+}
+
+impl Atom {
+    pub fn is_hydrogen(&self) -> bool {
+        self.atomic_number == 1
+    }
+}
+
+/// Atoms plus bonds with adjacency lists keyed by atom id.
+#[derive(Debug, Clone)]
+pub struct Molecule {
+    atoms: Vec<Atom>,
+    bonds: Vec<Bond>,
+    index: HashMap<u32, usize>,
+    /// Bond indices touching each atom, for every atom (isolated atoms map to an empty list).
+    adjacency: BTreeMap<u32, Vec<usize>>,
+    stereo: Vec<Stereo>,
+}
+
+impl Molecule {
+    /// Builds the graph from PubChem's parallel arrays. Bonds naming an atom id that isn't in
+    /// `atoms` are dropped.
+    pub fn new(atoms: &AtomInfo, bonds: Option<&BondInfo>, coords: Option<&[Coords]>) -> Self {
+        let conformer = coords.and_then(|coords| coords.first()).and_then(|c| c.conformers.first());
+        let atoms: Vec<Atom> = atoms
+            .aid
+            .iter()
+            .zip(&atoms.element)
+            .enumerate()
+            .map(|(idx, (&aid, &atomic_number))| Atom {
+                aid,
+                atomic_number,
+                element: Element::from_number(atomic_number),
+                charge: atoms.charge_of(aid),
+                isotope: atoms.isotope_of(aid),
+                radical: atoms.radical_of(aid),
+                position: conformer.and_then(|conformer| {
+                    let x = *conformer.x.get(idx)?;
+                    let y = *conformer.y.get(idx)?;
+                    let z = conformer.z.as_ref().and_then(|z| z.get(idx).copied()).unwrap_or(0.0);
+                    Some([x, y, z])
+                }),
+            })
+            .collect();
+        let index: HashMap<u32, usize> = atoms.iter().enumerate().map(|(idx, atom)| (atom.aid, idx)).collect();
+        let bonds: Vec<Bond> = bonds
+            .map(|bonds| {
+                bonds
+                    .iter()
+                    .filter(|bond| index.contains_key(&bond.aid1) && index.contains_key(&bond.aid2))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self::from_parts(atoms, bonds, index)
+    }
+
+    fn from_parts(atoms: Vec<Atom>, bonds: Vec<Bond>, index: HashMap<u32, usize>) -> Self {
+        let mut adjacency: BTreeMap<u32, Vec<usize>> = atoms.iter().map(|atom| (atom.aid, Vec::new())).collect();
+        for (idx, bond) in bonds.iter().enumerate() {
+            adjacency.entry(bond.aid1).or_default().push(idx);
+            if bond.aid2 != bond.aid1 {
+                adjacency.entry(bond.aid2).or_default().push(idx);
+            }
+        }
+        Molecule {
+            atoms,
+            bonds,
+            index,
+            adjacency,
+            stereo: Vec::new(),
+        }
+    }
+
+    /// Attaches stereo descriptors; ones naming an atom id that isn't in the molecule are dropped.
+    pub fn with_stereo(mut self, stereo: Vec<Stereo>) -> Self {
+        self.stereo = stereo
+            .into_iter()
+            .filter(|descriptor| descriptor.atoms().iter().all(|aid| self.index.contains_key(aid)))
+            .collect();
+        self
+    }
+
+    /// The molecule's graph, or `None` when the record carries no atoms.
+    pub fn from_compound(compound: &Compound) -> Option<Self> {
+        let atoms = compound.atoms.as_ref()?;
+        let molecule = Self::new(atoms, compound.bonds.as_ref(), compound.coords.as_deref());
+        Some(molecule.with_stereo(compound.stereo.clone().unwrap_or_default()))
+    }
+
+    pub fn atoms(&self) -> &[Atom] {
+        &self.atoms
+    }
+
+    pub fn bonds(&self) -> &[Bond] {
+        &self.bonds
+    }
+
+    /// Stereo descriptors of every geometry, in record order.
+    pub fn stereo(&self) -> &[Stereo] {
+        &self.stereo
+    }
+
+    /// Descriptors centered on atom `aid`, or for planar ones, with `aid` at either end of the
+    /// double bond.
+    pub fn stereo_at(&self, aid: u32) -> impl Iterator<Item = &Stereo> + '_ {
+        self.stereo.iter().filter(move |descriptor| match descriptor {
+            Stereo::Planar(planar) => planar.left == aid || planar.right == aid,
+            _ => descriptor.center() == Some(aid),
+        })
+    }
+
+    pub fn atom(&self, aid: u32) -> Option<&Atom> {
+        self.index.get(&aid).map(|&idx| &self.atoms[idx])
+    }
+
+    /// Bonds touching atom `aid`.
+    pub fn bonds_of(&self, aid: u32) -> impl Iterator<Item = &Bond> + '_ {
+        self.adjacency
+            .get(&aid)
+            .into_iter()
+            .flatten()
+            .map(move |&idx| &self.bonds[idx])
+    }
+
+    /// Atoms bonded to atom `aid`, in bond order.
+    pub fn neighbors(&self, aid: u32) -> impl Iterator<Item = &Atom> + '_ {
+        self.bonds_of(aid).filter_map(move |bond| {
+            let other = if bond.aid1 == aid { bond.aid2 } else { bond.aid1 };
+            self.atom(other)
+        })
+    }
+
+    /// Number of explicit bonds to atom `aid`.
+    pub fn degree(&self, aid: u32) -> usize {
+        self.adjacency.get(&aid).map_or(0, Vec::len)
+    }
+
+    /// Sum of covalent bond orders at atom `aid`; dative, ionic and unknown bonds count as 0.
+    pub fn bond_order_sum(&self, aid: u32) -> u32 {
+        self.bonds_of(aid).map(|bond| covalent_order(bond.order)).sum()
+    }
+
+    /// Hydrogens implied by the element's valence but not present as explicit atoms.
+    ///
+    /// Picks the smallest common valence, adjusted for formal charge, that covers the explicit
+    /// bond orders plus any radical electrons. PubChem records list hydrogens explicitly, so this
+    /// is usually 0 for them; metals and elements without a typical valence never get implicit
+    /// hydrogens.
+    pub fn implicit_hydrogens(&self, aid: u32) -> u32 {
+        let Some(atom) = self.atom(aid) else {
+            return 0;
+        };
+        let Some(element) = atom.element.filter(|element| takes_implicit_hydrogens(element.number)) else {
+            return 0;
+        };
+        let used = (self.bond_order_sum(aid) + atom.radical.map_or(0, Radical::electrons)) as i32;
+        element
+            .valences
+            .iter()
+            .map(|&valence| charged_valence(element.number, valence as i32, atom.charge))
+            .find(|&valence| valence >= used)
+            .map_or(0, |valence| (valence - used) as u32)
+    }
+
+    /// Explicit hydrogen neighbors plus implicit hydrogens of atom `aid`.
+    pub fn hydrogen_count(&self, aid: u32) -> u32 {
+        let explicit = self.neighbors(aid).filter(|atom| atom.is_hydrogen()).count() as u32;
+        explicit + self.implicit_hydrogens(aid)
+    }
+
+    /// Splits the graph into its connected pieces (e.g. the ions of a salt), largest first;
+    /// ties keep the order of their first atom.
+    pub fn components(&self) -> Vec<Molecule> {
+        let mut seen: HashMap<u32, usize> = HashMap::new();
+        let mut groups = 0;
+        for atom in &self.atoms {
+            if seen.contains_key(&atom.aid) {
+                continue;
+            }
+            let group = groups;
+            groups += 1;
+            let mut queue = VecDeque::from([atom.aid]);
+            seen.insert(atom.aid, group);
+            while let Some(aid) = queue.pop_front() {
+                for neighbor in self.neighbors(aid) {
+                    if let Entry::Vacant(entry) = seen.entry(neighbor.aid) {
+                        entry.insert(group);
+                        queue.push_back(neighbor.aid);
+                    }
+                }
+            }
+        }
+        let mut components: Vec<Molecule> = (0..groups)
+            .map(|group| {
+                let atoms: Vec<Atom> = self
+                    .atoms
+                    .iter()
+                    .filter(|atom| seen.get(&atom.aid) == Some(&group))
+                    .cloned()
+                    .collect();
+                let bonds: Vec<Bond> = self
+                    .bonds
+                    .iter()
+                    .filter(|bond| seen.get(&bond.aid1) == Some(&group))
+                    .copied()
+                    .collect();
+                let index = atoms.iter().enumerate().map(|(idx, atom)| (atom.aid, idx)).collect();
+                Molecule::from_parts(atoms, bonds, index).with_stereo(self.stereo.clone())
+            })
+            .collect();
+        components.sort_by_key(|component| std::cmp::Reverse(component.atoms.len()));
+        components
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.components().len() <= 1
+    }
+}
+
+impl Compound {
+    /// Shorthand for [`Molecule::from_compound`].
+    pub fn molecule(&self) -> Option<Molecule> {
+        Molecule::from_compound(self)
+    }
+}
+
+/// PubChem bond orders 1-4 are single to quadruple; 5+ (dative, complex, ionic) and 255 (unknown)
+/// don't use up valence.
+pub(crate) fn covalent_order(order: u32) -> u32 {
+    if (1..=4).contains(&order) {
+        order
+    } else {
+        0
+    }
+}
+
+/// Organic-subset elements plus the heavier main-group non-metals. Hydrogen is left out: a lone
+/// H, H+ or H- is exactly what the record says it is.
+fn takes_implicit_hydrogens(number: u32) -> bool {
+    matches!(number, 5..=9 | 14..=17 | 33..=35 | 52 | 53)
+}
+
+/// Boron is isoelectronic with the next element when negative (B- like C) and loses a bond when
+/// positive; carbon and silicon lose a bond per unit of charge either way; electron-rich elements
+/// gain one per positive charge (N+ like C) and lose one per negative charge (O- like F).
+fn charged_valence(number: u32, valence: i32, charge: i32) -> i32 {
+    match number {
+        5 => valence - charge,
+        6 | 14 => valence - charge.abs(),
+        _ => valence + charge,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compound::{parse_compounds, Parity};
+
+    const ASPIRIN: &str = include_str!("../tests/data/compound_cid_2244.json");
+    const L_ALANINE: &str = include_str!("../tests/data/compound_cid_5950.json");
+    const SODIUM_ACETATE: &str = include_str!("../tests/data/compound_cid_517045.json");
+
+    fn molecule(body: &str) -> Molecule {
+        parse_compounds(body).unwrap()[0].molecule().unwrap()
+    }
+
+    #[test]
+    fn builds_adjacency_from_compound() {
+        let aspirin = molecule(ASPIRIN);
+        assert_eq!(aspirin.atoms().len(), 21);
+        assert_eq!(aspirin.bonds().len(), 21);
+        let carboxyl_carbon = aspirin.atom(10).unwrap();
+        assert_eq!(carboxyl_carbon.element.unwrap().symbol, "C");
+        assert!(carboxyl_carbon.position.is_some());
+        for atom in aspirin.atoms() {
+            let degree = aspirin.degree(atom.aid);
+            assert_eq!(aspirin.neighbors(atom.aid).count(), degree);
+            if atom.is_hydrogen() {
+                assert_eq!(degree, 1);
+            }
+        }
+    }
+
+    #[test]
+    fn counts_hydrogens_and_valence() {
+        let aspirin = molecule(ASPIRIN);
+        let total_h: u32 = aspirin
+            .atoms()
+            .iter()
+            .filter(|atom| !atom.is_hydrogen())
+            .map(|atom| aspirin.hydrogen_count(atom.aid))
+            .sum();
+        assert_eq!(total_h, 8);
+        for atom in aspirin.atoms() {
+            assert_eq!(aspirin.implicit_hydrogens(atom.aid), 0);
+        }
+        let carbons_at_four = aspirin
+            .atoms()
+            .iter()
+            .filter(|atom| atom.atomic_number == 6)
+            .all(|atom| aspirin.bond_order_sum(atom.aid) == 4);
+        assert!(carbons_at_four);
+    }
+
+    #[test]
+    fn infers_implicit_hydrogens_on_a_heavy_atom_skeleton() {
+        let atoms = AtomInfo {
+            aid: vec![1, 2, 3, 4],
+            element: vec![6, 6, 8, 7],
+            charge: vec![crate::compound::AtomInt { aid: 4, value: 1 }],
+            isotope: Vec::new(),
+            radical: Vec::new(),
+        };
+        let bonds = BondInfo {
+            aid1: vec![1, 2, 2],
+            aid2: vec![2, 3, 4],
+            order: vec![1, 2, 1],
+        };
+        let acetamidium = Molecule::new(&atoms, Some(&bonds), None);
+        assert_eq!(acetamidium.implicit_hydrogens(1), 3);
+        assert_eq!(acetamidium.implicit_hydrogens(2), 0);
+        assert_eq!(acetamidium.implicit_hydrogens(3), 0);
+        assert_eq!(acetamidium.implicit_hydrogens(4), 3);
+    }
+
+    #[test]
+    fn exposes_stereo_descriptors() {
+        let alanine = molecule(L_ALANINE);
+        assert_eq!(alanine.stereo().len(), 1);
+        assert_eq!(alanine.stereo_at(4).count(), 1);
+        assert_eq!(alanine.stereo_at(1).count(), 0);
+        assert_eq!(alanine.stereo()[0].parity(), Some(Parity::Clockwise));
+        assert!(molecule(ASPIRIN).stereo().is_empty());
+    }
+
+    #[test]
+    fn leaves_hydrons_and_hydrides_alone() {
+        for charge in [1, 0, -1] {
+            let atoms = AtomInfo {
+                aid: vec![1],
+                element: vec![1],
+                charge: vec![crate::compound::AtomInt { aid: 1, value: charge }],
+                isotope: Vec::new(),
+                radical: Vec::new(),
+            };
+            let hydrogen = Molecule::new(&atoms, None, None);
+            assert_eq!(hydrogen.implicit_hydrogens(1), 0, "charge {}", charge);
+            assert_eq!(hydrogen.hydrogen_count(1), 0);
+        }
+    }
+
+    #[test]
+    fn fills_borate_and_borenium_skeletons() {
+        let boron = |charge: i32| AtomInfo {
+            aid: vec![1],
+            element: vec![5],
+            charge: vec![crate::compound::AtomInt { aid: 1, value: charge }],
+            isotope: Vec::new(),
+            radical: Vec::new(),
+        };
+        assert_eq!(Molecule::new(&boron(-1), None, None).implicit_hydrogens(1), 4);
+        assert_eq!(Molecule::new(&boron(0), None, None).implicit_hydrogens(1), 3);
+        assert_eq!(Molecule::new(&boron(1), None, None).implicit_hydrogens(1), 2);
+    }
+
+    #[test]
+    fn splits_salts_into_components() {
+        let salt = molecule(SODIUM_ACETATE);
+        assert!(!salt.is_connected());
+        assert_eq!(salt.atom(1).unwrap().charge, -1);
+        assert_eq!(salt.atom(3).unwrap().charge, 1);
+        assert_eq!(salt.implicit_hydrogens(1), 0);
+
+        let components = salt.components();
+        assert_eq!(components.len(), 2);
+        assert_eq!(components[0].atoms().len(), 7);
+        assert_eq!(components[0].bonds().len(), 6);
+        assert_eq!(components[1].atoms().len(), 1);
+        assert_eq!(components[1].atoms()[0].element.unwrap().symbol, "Na");
+        assert_eq!(components[1].degree(3), 0);
+        assert!(components[0].is_connected());
+    }
+}
@@ -0,0 +1,107 @@
+//! Input namespaces for the PUG REST `compound` domain.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// How an identifier passed to [`PubChemClient`](crate::PubChemClient) should be interpreted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Namespace {
+    Name,
+    Cid,
+    Smiles,
+    Inchi,
+    InchiKey,
+    Formula,
+    ListKey,
+}
+
+impl Namespace {
+    pub const ALL: [Namespace; 7] = [
+        Namespace::Name,
+        Namespace::Cid,
+        Namespace::Smiles,
+        Namespace::Inchi,
+        Namespace::InchiKey,
+        Namespace::Formula,
+        Namespace::ListKey,
+    ];
+
+    /// Path segment PUG REST uses for this namespace.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Namespace::Name => "name",
+            Namespace::Cid => "cid",
+            Namespace::Smiles => "smiles",
+            Namespace::Inchi => "inchi",
+            Namespace::InchiKey => "inchikey",
+            Namespace::Formula => "formula",
+            Namespace::ListKey => "listkey",
+        }
+    }
+
+    /// SMILES and InChI routinely contain `/`, which PUG REST cannot take in a URL
+    /// path, so those identifiers are sent as a form-encoded POST body instead.
+    pub fn uses_post(&self) -> bool {
+        matches!(self, Namespace::Smiles | Namespace::Inchi)
+    }
+
+    /// Whether PUG REST accepts a comma-separated list of identifiers in one request.
+    pub fn supports_list_input(&self) -> bool {
+        matches!(self, Namespace::Cid | Namespace::InchiKey)
+    }
+}
+
+impl fmt::Display for Namespace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Namespace {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Namespace::ALL
+            .into_iter()
+            .find(|ns| ns.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                let names: Vec<&str> = Namespace::ALL.iter().map(Namespace::as_str).collect();
+                format!("unknown namespace '{}' (expected one of: {})", s, names.join(", "))
+            })
+    }
+}
+
+/// Percent-encodes a single URL path segment, leaving only RFC 3986 unreserved characters.
+pub(crate) fn encode_path_segment(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_namespace_names() {
+        for ns in Namespace::ALL {
+            assert_eq!(ns.as_str().parse::<Namespace>(), Ok(ns));
+        }
+        assert_eq!("InChIKey".parse::<Namespace>(), Ok(Namespace::InchiKey));
+        assert!("sid".parse::<Namespace>().is_err());
+    }
+
+    #[test]
+    fn encodes_reserved_characters() {
+        assert_eq!(encode_path_segment("aspirin"), "aspirin");
+        assert_eq!(encode_path_segment("C#N"), "C%23N");
+        assert_eq!(encode_path_segment("C/C=C/C"), "C%2FC%3DC%2FC");
+        assert_eq!(encode_path_segment("vitamin b12"), "vitamin%20b12");
+        assert_eq!(encode_path_segment("β-carotene"), "%CE%B2-carotene");
+    }
+}
@@ -0,0 +1,157 @@
+//! 2D structure rendering with plotters.
+
+use crate::compound::{AtomInfo, BondInfo, Conformer, Coords};
+use crate::molecule::covalent_order;
+use plotters::prelude::*;
+use std::path::Path;
+
+/// Returns padded (x, y) plotting ranges that enclose every atom of the conformer.
+fn conformer_bounds(conformer: &Conformer) -> (std::ops::Range<f64>, std::ops::Range<f64>) {
+    let span = |values: &[f64]| {
+        let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+        let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        if min.is_finite() && max.is_finite() {
+            (min - 1.0)..(max + 1.0)
+        } else {
+            0f64..10f64
+        }
+    };
+    (span(&conformer.x), span(&conformer.y))
+}
+
+/// Symbol with isotope, charge and radical marks, e.g. `13C`, `O-`, `Ca2+`, `C•`; `None` for a
+/// plain carbon, which the skeleton already implies.
+fn atom_label(atoms: &AtomInfo, aid: u32, symbol: &str) -> Option<String> {
+    let isotope = atoms.isotope_of(aid);
+    let charge = atoms.charge_of(aid);
+    let radical = atoms.radical_of(aid);
+    if symbol == "C" && isotope.is_none() && charge == 0 && radical.is_none() {
+        return None;
+    }
+    let mut label = isotope.map_or_else(String::new, |isotope| isotope.to_string());
+    label.push_str(symbol);
+    match charge {
+        0 => {}
+        1 => label.push('+'),
+        -1 => label.push('-'),
+        charge if charge > 0 => label.push_str(&format!("{}+", charge)),
+        charge => label.push_str(&format!("{}-", -charge)),
+    }
+    if radical.is_some() {
+        label.push('•');
+    }
+    Some(label)
+}
+
+/// Draws the first conformer's bond skeleton to a PNG at `png_path`, captioned with `title`.
+///
+/// Returns how many bonds were left out because an end atom has no coordinates.
+pub fn plot_molecule(
+    png_path: &Path,
+    title: &str,
+    atoms: &AtomInfo,
+    coords: &[Coords],
+    bonds: &BondInfo,
+) -> Result<usize, Box<dyn std::error::Error>> {
+    let Some(conformer) = coords.first().and_then(|c| c.conformers.first()) else {
+        eprintln!("No conformers available for plotting.");
+        return Ok(0);
+    };
+    let root = BitMapBackend::new(png_path, (800, 600)).into_drawing_area();
+    root.fill(&WHITE)?;
+    let (x_range, y_range) = conformer_bounds(conformer);
+    let mut chart = ChartBuilder::on(&root)
+        .caption(title, ("sans-serif", 50).into_font())
+        .margin(20)
+        .x_label_area_size(40)
+        .y_label_area_size(40)
+        .build_cartesian_2d(x_range, y_range)?;
+    chart.configure_mesh().draw()?;
+    let index = atoms.aid_index();
+    let position = |aid: u32| {
+        index
+            .get(&aid)
+            .filter(|&&idx| idx < conformer.x.len() && idx < conformer.y.len())
+            .map(|&idx| (conformer.x[idx], conformer.y[idx]))
+    };
+    let mut skipped = 0;
+    for bond in bonds.iter() {
+        match (position(bond.aid1), position(bond.aid2)) {
+            (Some(start), Some(end)) => {
+                // Dative, ionic and unknown bonds (orders 5+ and 255) are drawn as single lines.
+                let style = RED.stroke_width(covalent_order(bond.order).max(1));
+                chart.draw_series(LineSeries::new(vec![start, end], style))?;
+            }
+            _ => skipped += 1,
+        }
+    }
+    // Atoms in CPK colors with a black rim so white hydrogens stay visible; non-carbon atoms and
+    // annotated carbons get a label.
+    for (&aid, element) in atoms.aid.iter().zip(atoms.elements()) {
+        let Some(point) = position(aid) else {
+            continue;
+        };
+        let color = element.map_or(BLACK, |element| {
+            let (r, g, b) = element.cpk_rgb();
+            RGBColor(r, g, b)
+        });
+        chart.draw_series([
+            Circle::new(point, 5, color.filled()),
+            Circle::new(point, 5, BLACK.stroke_width(1)),
+        ])?;
+        if let Some(text) = element.and_then(|element| atom_label(atoms, aid, element.symbol)) {
+            let label = EmptyElement::at(point) + Text::new(text, (6, -22), ("sans-serif", 18).into_font());
+            chart.draw_series([label])?;
+        }
+    }
+    root.present()?;
+    Ok(skipped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compound::{parse_compounds, AtomInt, AtomRadical};
+
+    const ASPIRIN: &str = include_str!("../tests/data/compound_cid_2244.json");
+
+    #[test]
+    fn conformer_bounds_enclose_all_atoms() {
+        let compounds = parse_compounds(ASPIRIN).unwrap();
+        let conformer = &compounds[0].coords.as_ref().unwrap()[0].conformers[0];
+        let (x_range, y_range) = conformer_bounds(conformer);
+        assert!(conformer.x.iter().all(|x| x_range.contains(x)));
+        assert!(conformer.y.iter().all(|y| y_range.contains(y)));
+    }
+
+    #[test]
+    fn counts_bonds_without_coordinates() {
+        let mut compound = parse_compounds(ASPIRIN).unwrap().remove(0);
+        let mut bonds = compound.bonds.take().unwrap();
+        // A dative bond to an atom the conformer doesn't place.
+        bonds.aid1.push(1);
+        bonds.aid2.push(99);
+        bonds.order.push(5);
+        let png_path = std::env::temp_dir().join(format!("pcpug-plot-{}.png", std::process::id()));
+        let atoms = compound.atoms.as_ref().unwrap();
+        let skipped = plot_molecule(&png_path, "aspirin", atoms, compound.coords.as_ref().unwrap(), &bonds).unwrap();
+        let _ = std::fs::remove_file(&png_path);
+        assert_eq!(skipped, 1);
+    }
+
+    #[test]
+    fn labels_show_isotopes_charges_and_radicals() {
+        let atoms = AtomInfo {
+            aid: vec![1, 2, 3, 4, 5],
+            element: vec![6, 6, 8, 20, 6],
+            charge: vec![AtomInt { aid: 3, value: -1 }, AtomInt { aid: 4, value: 2 }],
+            isotope: vec![AtomInt { aid: 2, value: 13 }],
+            radical: vec![AtomRadical { aid: 5, kind: 2 }],
+        };
+        assert_eq!(atom_label(&atoms, 1, "C"), None);
+        assert_eq!(atom_label(&atoms, 2, "C").as_deref(), Some("13C"));
+        assert_eq!(atom_label(&atoms, 3, "O").as_deref(), Some("O-"));
+        assert_eq!(atom_label(&atoms, 4, "Ca").as_deref(), Some("Ca2+"));
+        assert_eq!(atom_label(&atoms, 5, "C").as_deref(), Some("C•"));
+    }
+}
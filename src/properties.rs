@@ -0,0 +1,223 @@
+//! PUG REST property tables (`compound/.../property/<names>/JSON`).
+
+use serde::{Deserialize, Deserializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// Comma-separated list values such as `AnnotationTypes` and `SourceCategories`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(transparent)]
+pub struct StringList(pub Vec<String>);
+
+impl FromStr for StringList {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(StringList(s.split(',').map(|item| item.trim().to_string()).collect()))
+    }
+}
+
+impl fmt::Display for StringList {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0.join(","))
+    }
+}
+
+/// PubChem sends some numeric properties (e.g. `MolecularWeight`) as strings; accept either form.
+fn lenient<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr + Deserialize<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Value<T> {
+        Typed(T),
+        Text(String),
+    }
+    match Option::<Value<T>>::deserialize(deserializer)? {
+        Some(Value::Typed(value)) => Ok(Some(value)),
+        Some(Value::Text(text)) => text
+            .parse()
+            .map(Some)
+            .map_err(|_| serde::de::Error::custom(format!("unexpected property value '{}'", text))),
+        None => Ok(None),
+    }
+}
+
+macro_rules! properties {
+    ($($(#[$doc:meta])* $name:literal => $variant:ident, $field:ident: $ty:ty;)*) => {
+        /// A property name accepted by the PUG REST property table endpoint.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum Property {
+            $($(#[$doc])* $variant,)*
+        }
+
+        impl Property {
+            pub const ALL: &'static [Property] = &[$(Property::$variant,)*];
+
+            /// Name used in the request path and the response columns.
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(Property::$variant => $name,)*
+                }
+            }
+        }
+
+        /// One row of a property table; columns that weren't requested are `None`.
+        #[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+        pub struct CompoundProperties {
+            #[serde(rename = "CID")]
+            pub cid: u32,
+            $(
+                #[serde(rename = $name, default, deserialize_with = "lenient")]
+                pub $field: Option<$ty>,
+            )*
+        }
+
+        impl CompoundProperties {
+            /// Display form of a single column, for tabular output.
+            pub fn value(&self, property: Property) -> Option<String> {
+                match property {
+                    $(Property::$variant => self.$field.as_ref().map(ToString::to_string),)*
+                }
+            }
+        }
+    };
+}
+
+properties! {
+    "MolecularFormula" => MolecularFormula, molecular_formula: String;
+    "MolecularWeight" => MolecularWeight, molecular_weight: f64;
+    "SMILES" => SMILES, smiles: String;
+    "ConnectivitySMILES" => ConnectivitySMILES, connectivity_smiles: String;
+    /// Deprecated by PubChem in favour of `ConnectivitySMILES`.
+    "CanonicalSMILES" => CanonicalSMILES, canonical_smiles: String;
+    /// Deprecated by PubChem in favour of `SMILES`.
+    "IsomericSMILES" => IsomericSMILES, isomeric_smiles: String;
+    "InChI" => InChI, inchi: String;
+    "InChIKey" => InChIKey, inchikey: String;
+    "IUPACName" => IUPACName, iupac_name: String;
+    "Title" => Title, title: String;
+    "XLogP" => XLogP, xlogp: f64;
+    "ExactMass" => ExactMass, exact_mass: f64;
+    "MonoisotopicMass" => MonoisotopicMass, monoisotopic_mass: f64;
+    "TPSA" => TPSA, tpsa: f64;
+    "Complexity" => Complexity, complexity: f64;
+    "Charge" => Charge, charge: i32;
+    "HBondDonorCount" => HBondDonorCount, h_bond_donor_count: u32;
+    "HBondAcceptorCount" => HBondAcceptorCount, h_bond_acceptor_count: u32;
+    "RotatableBondCount" => RotatableBondCount, rotatable_bond_count: u32;
+    "HeavyAtomCount" => HeavyAtomCount, heavy_atom_count: u32;
+    "IsotopeAtomCount" => IsotopeAtomCount, isotope_atom_count: u32;
+    "AtomStereoCount" => AtomStereoCount, atom_stereo_count: u32;
+    "DefinedAtomStereoCount" => DefinedAtomStereoCount, defined_atom_stereo_count: u32;
+    "UndefinedAtomStereoCount" => UndefinedAtomStereoCount, undefined_atom_stereo_count: u32;
+    "BondStereoCount" => BondStereoCount, bond_stereo_count: u32;
+    "DefinedBondStereoCount" => DefinedBondStereoCount, defined_bond_stereo_count: u32;
+    "UndefinedBondStereoCount" => UndefinedBondStereoCount, undefined_bond_stereo_count: u32;
+    "CovalentUnitCount" => CovalentUnitCount, covalent_unit_count: u32;
+    "PatentCount" => PatentCount, patent_count: u32;
+    "PatentFamilyCount" => PatentFamilyCount, patent_family_count: u32;
+    "LiteratureCount" => LiteratureCount, literature_count: u32;
+    "AnnotationTypes" => AnnotationTypes, annotation_types: StringList;
+    "AnnotationTypeCount" => AnnotationTypeCount, annotation_type_count: u32;
+    "SourceCategories" => SourceCategories, source_categories: StringList;
+    "Volume3D" => Volume3D, volume_3d: f64;
+    "XStericQuadrupole3D" => XStericQuadrupole3D, x_steric_quadrupole_3d: f64;
+    "YStericQuadrupole3D" => YStericQuadrupole3D, y_steric_quadrupole_3d: f64;
+    "ZStericQuadrupole3D" => ZStericQuadrupole3D, z_steric_quadrupole_3d: f64;
+    "FeatureCount3D" => FeatureCount3D, feature_count_3d: u32;
+    "FeatureAcceptorCount3D" => FeatureAcceptorCount3D, feature_acceptor_count_3d: u32;
+    "FeatureDonorCount3D" => FeatureDonorCount3D, feature_donor_count_3d: u32;
+    "FeatureAnionCount3D" => FeatureAnionCount3D, feature_anion_count_3d: u32;
+    "FeatureCationCount3D" => FeatureCationCount3D, feature_cation_count_3d: u32;
+    "FeatureRingCount3D" => FeatureRingCount3D, feature_ring_count_3d: u32;
+    "FeatureHydrophobeCount3D" => FeatureHydrophobeCount3D, feature_hydrophobe_count_3d: u32;
+    "ConformerModelRMSD3D" => ConformerModelRMSD3D, conformer_model_rmsd_3d: f64;
+    "EffectiveRotorCount3D" => EffectiveRotorCount3D, effective_rotor_count_3d: f64;
+    "ConformerCount3D" => ConformerCount3D, conformer_count_3d: u32;
+    "Fingerprint2D" => Fingerprint2D, fingerprint_2d: String;
+}
+
+impl fmt::Display for Property {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Property {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Property::ALL
+            .iter()
+            .copied()
+            .find(|property| property.as_str().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| format!("unknown property '{}'", s))
+    }
+}
+
+/// Comma-separated path segment naming `properties`.
+pub(crate) fn property_list(properties: &[Property]) -> String {
+    properties.iter().map(Property::as_str).collect::<Vec<_>>().join(",")
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct PropertyTableEnvelope {
+    #[serde(rename = "PropertyTable")]
+    pub table: PropertyTable,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct PropertyTable {
+    #[serde(rename = "Properties", default)]
+    pub properties: Vec<CompoundProperties>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TABLE: &str = include_str!("../tests/data/property_table_2244_5950.json");
+
+    #[test]
+    fn parses_property_table() {
+        let envelope: PropertyTableEnvelope = serde_json::from_str(TABLE).unwrap();
+        let rows = envelope.table.properties;
+        assert_eq!(rows.len(), 2);
+        let aspirin = &rows[0];
+        assert_eq!(aspirin.cid, 2244);
+        assert_eq!(aspirin.molecular_formula.as_deref(), Some("C9H8O4"));
+        assert_eq!(aspirin.molecular_weight, Some(180.16));
+        assert_eq!(aspirin.xlogp, Some(1.2));
+        assert_eq!(aspirin.tpsa, Some(63.6));
+        assert_eq!(aspirin.h_bond_donor_count, Some(1));
+        assert_eq!(aspirin.charge, Some(0));
+        assert_eq!(aspirin.volume_3d, Some(136.0));
+        assert!(aspirin.inchi.is_none());
+        assert_eq!(rows[1].xlogp, Some(-3.0));
+    }
+
+    #[test]
+    fn formats_columns() {
+        let envelope: PropertyTableEnvelope = serde_json::from_str(TABLE).unwrap();
+        let aspirin = &envelope.table.properties[0];
+        assert_eq!(aspirin.value(Property::MolecularWeight).as_deref(), Some("180.16"));
+        assert_eq!(aspirin.value(Property::Complexity).as_deref(), Some("212"));
+        assert_eq!(aspirin.value(Property::InChI), None);
+    }
+
+    #[test]
+    fn parses_property_names() {
+        for property in Property::ALL {
+            assert_eq!(property.as_str().parse::<Property>(), Ok(*property));
+        }
+        assert_eq!("tpsa".parse::<Property>(), Ok(Property::TPSA));
+        assert!("Colour".parse::<Property>().is_err());
+        assert_eq!(
+            property_list(&[Property::MolecularFormula, Property::XLogP]),
+            "MolecularFormula,XLogP"
+        );
+    }
+}
@@ -0,0 +1,62 @@
+//! PUG REST structure searches (`fastsubstructure`, `fastsimilarity_2d`, ...).
+
+/// Kind of structure search to run against PubChem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchKind {
+    /// Compounds containing the query as a substructure.
+    Substructure,
+    /// Compounds contained in the query.
+    Superstructure,
+    /// 2D Tanimoto similarity at or above `threshold` percent (PubChem's default is 90).
+    Similarity { threshold: u8 },
+    /// Compounds identical to the query.
+    Identity,
+}
+
+impl SearchKind {
+    /// Path segment PUG REST uses for this search.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SearchKind::Substructure => "fastsubstructure",
+            SearchKind::Superstructure => "fastsuperstructure",
+            SearchKind::Similarity { .. } => "fastsimilarity_2d",
+            SearchKind::Identity => "fastidentity",
+        }
+    }
+}
+
+/// Optional limits shared by every search kind.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchOptions {
+    /// Stop after this many hits (`MaxRecords`).
+    pub max_records: Option<u32>,
+}
+
+impl SearchKind {
+    /// Query-string parameters for this search and `options`.
+    pub(crate) fn query_params(&self, options: &SearchOptions) -> Vec<(&'static str, String)> {
+        let mut params = Vec::new();
+        if let SearchKind::Similarity { threshold } = self {
+            params.push(("Threshold", threshold.to_string()));
+        }
+        if let Some(max_records) = options.max_records {
+            params.push(("MaxRecords", max_records.to_string()));
+        }
+        params
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn similarity_sends_threshold_and_max_records() {
+        let params = SearchKind::Similarity { threshold: 95 }.query_params(&SearchOptions { max_records: Some(50) });
+        assert_eq!(
+            params,
+            vec![("Threshold", "95".to_string()), ("MaxRecords", "50".to_string())]
+        );
+        assert!(SearchKind::Identity.query_params(&SearchOptions::default()).is_empty());
+    }
+}
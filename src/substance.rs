@@ -0,0 +1,133 @@
+//! Substance (SID) records from the PUG REST `substance` domain.
+
+use serde::Deserialize;
+use std::fmt;
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct PcSubstances {
+    #[serde(rename = "PC_Substances")]
+    pub substances: Vec<PcSubstance>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct PcSubstance {
+    sid: PcSid,
+    source: Option<PcSource>,
+    #[serde(default)]
+    synonyms: Vec<String>,
+    #[serde(default)]
+    compound: Vec<PcSubstanceCompound>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PcSid {
+    id: u32,
+    version: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PcSource {
+    db: Option<PcSourceDb>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PcSourceDb {
+    name: Option<String>,
+    source_id: Option<PcSourceId>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PcSourceId {
+    #[serde(rename = "str")]
+    value: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PcSubstanceCompound {
+    id: PcSubstanceCompoundId,
+}
+
+#[derive(Debug, Deserialize)]
+struct PcSubstanceCompoundId {
+    #[serde(rename = "type")]
+    kind: u32,
+    id: Option<PcSubstanceCid>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PcSubstanceCid {
+    cid: u32,
+}
+
+/// `PC-CompoundType` value PubChem uses for the standardized form of a deposited substance.
+const STANDARDIZED: u32 = 1;
+
+/// A depositor-supplied substance and the compounds PubChem standardized it to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Substance {
+    pub sid: u32,
+    pub version: Option<u32>,
+    pub source_name: Option<String>,
+    /// The depositor's own identifier for the record.
+    pub source_id: Option<String>,
+    pub synonyms: Vec<String>,
+    /// CIDs of the standardized compounds; empty when standardization failed.
+    pub cids: Vec<u32>,
+}
+
+impl From<PcSubstance> for Substance {
+    fn from(record: PcSubstance) -> Self {
+        let db = record.source.and_then(|source| source.db);
+        let (source_name, source_id) = match db {
+            Some(db) => (db.name, db.source_id.and_then(|id| id.value)),
+            None => (None, None),
+        };
+        Substance {
+            sid: record.sid.id,
+            version: record.sid.version,
+            source_name,
+            source_id,
+            synonyms: record.synonyms,
+            cids: record
+                .compound
+                .into_iter()
+                .filter(|compound| compound.id.kind == STANDARDIZED)
+                .filter_map(|compound| compound.id.id.map(|id| id.cid))
+                .collect(),
+        }
+    }
+}
+
+impl fmt::Display for Substance {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Substance Information:")?;
+        writeln!(f, "---------------------")?;
+        writeln!(f, "SID: {}", self.sid)?;
+        writeln!(f, "Version: {:?}", self.version)?;
+        writeln!(f, "Source: {}", self.source_name.as_deref().unwrap_or("Unknown"))?;
+        writeln!(f, "Source ID: {}", self.source_id.as_deref().unwrap_or("Unknown"))?;
+        writeln!(f, "Standardized CIDs: {:?}", self.cids)?;
+        writeln!(f, "Synonyms:")?;
+        for synonym in &self.synonyms {
+            writeln!(f, "  {}", synonym)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_substance_record() {
+        let response: PcSubstances = serde_json::from_str(include_str!("../tests/data/substance_sid_135196.json")).unwrap();
+        let substance: Substance = response.substances.into_iter().next().unwrap().into();
+        assert_eq!(substance.sid, 135196);
+        assert_eq!(substance.version, Some(6));
+        assert_eq!(substance.source_name.as_deref(), Some("NIST Chemistry WebBook"));
+        assert_eq!(substance.source_id.as_deref(), Some("50-78-2"));
+        assert_eq!(substance.synonyms.len(), 3);
+        assert_eq!(substance.cids, [2244]);
+    }
+}
@@ -0,0 +1,224 @@
+//! Client-side rate limiting for PubChem's usage policy.
+//!
+//! PubChem asks for no more than 5 requests per second and 400 per minute, and reports
+//! how close a client is to being throttled in the `X-Throttling-Control` response header.
+
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Response header PubChem uses to report throttling state.
+pub const THROTTLING_HEADER: &str = "X-Throttling-Control";
+
+/// Request budget enforced by [`PubChemClient`](crate::PubChemClient).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    pub per_second: u32,
+    pub per_minute: u32,
+}
+
+impl Default for RateLimit {
+    /// PubChem's published policy: 5 requests per second, 400 per minute.
+    fn default() -> Self {
+        RateLimit {
+            per_second: 5,
+            per_minute: 400,
+        }
+    }
+}
+
+/// Worst status reported in an `X-Throttling-Control` header.
+///
+/// A header looks like `Request Count status: Green (0%), Request Time status: Yellow (52%),
+/// Service status: Green (20%)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ThrottlingStatus {
+    Green,
+    Yellow,
+    Red,
+    Black,
+}
+
+impl ThrottlingStatus {
+    /// Pause applied before the next request after this status was reported.
+    pub fn backoff(&self) -> Duration {
+        match self {
+            ThrottlingStatus::Green => Duration::ZERO,
+            ThrottlingStatus::Yellow => Duration::from_secs(1),
+            ThrottlingStatus::Red => Duration::from_secs(5),
+            ThrottlingStatus::Black => Duration::from_secs(60),
+        }
+    }
+}
+
+impl FromStr for ThrottlingStatus {
+    type Err = ();
+
+    fn from_str(header: &str) -> Result<Self, Self::Err> {
+        header
+            .split(',')
+            .filter_map(|part| part.split_once("status:"))
+            .filter_map(|(_, value)| match value.split_whitespace().next()? {
+                "Green" => Some(ThrottlingStatus::Green),
+                "Yellow" => Some(ThrottlingStatus::Yellow),
+                "Red" => Some(ThrottlingStatus::Red),
+                "Black" => Some(ThrottlingStatus::Black),
+                _ => None,
+            })
+            .max()
+            .ok_or(())
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    capacity: f64,
+    tokens: f64,
+    refill_per_sec: f64,
+}
+
+impl TokenBucket {
+    fn new(capacity: u32, period: Duration) -> Self {
+        let capacity = f64::from(capacity.max(1));
+        TokenBucket {
+            capacity,
+            tokens: capacity,
+            refill_per_sec: capacity / period.as_secs_f64(),
+        }
+    }
+
+    fn refill(&mut self, elapsed: Duration) {
+        self.tokens = (self.tokens + elapsed.as_secs_f64() * self.refill_per_sec).min(self.capacity);
+    }
+
+    /// Time until one token is available.
+    fn wait_time(&self) -> Duration {
+        if self.tokens >= 1.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64((1.0 - self.tokens) / self.refill_per_sec)
+        }
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    second: TokenBucket,
+    minute: TokenBucket,
+    last_refill: Instant,
+    paused_until: Option<Instant>,
+    status: Option<ThrottlingStatus>,
+}
+
+/// Token-bucket limiter shared by every clone of a client.
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    state: Mutex<State>,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(limit: RateLimit) -> Self {
+        RateLimiter {
+            state: Mutex::new(State {
+                second: TokenBucket::new(limit.per_second, Duration::from_secs(1)),
+                minute: TokenBucket::new(limit.per_minute, Duration::from_secs(60)),
+                last_refill: Instant::now(),
+                paused_until: None,
+                status: None,
+            }),
+        }
+    }
+
+    /// Waits until a request may be sent, then consumes one token from each bucket.
+    pub(crate) async fn acquire(&self) {
+        loop {
+            let wait = {
+                let mut state = self.state.lock().unwrap();
+                let now = Instant::now();
+                let elapsed = now.duration_since(state.last_refill);
+                state.second.refill(elapsed);
+                state.minute.refill(elapsed);
+                state.last_refill = now;
+                let paused = state
+                    .paused_until
+                    .map_or(Duration::ZERO, |until| until.saturating_duration_since(now));
+                let wait = paused.max(state.second.wait_time()).max(state.minute.wait_time());
+                if wait.is_zero() {
+                    state.second.tokens -= 1.0;
+                    state.minute.tokens -= 1.0;
+                    return;
+                }
+                wait
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Records the server's throttling header and pauses if it asks us to slow down.
+    pub(crate) fn observe(&self, header: &str) {
+        let Ok(status) = header.parse::<ThrottlingStatus>() else {
+            return;
+        };
+        let mut state = self.state.lock().unwrap();
+        state.status = Some(status);
+        let backoff = status.backoff();
+        if !backoff.is_zero() {
+            let until = Instant::now() + backoff;
+            state.paused_until = Some(state.paused_until.map_or(until, |current| current.max(until)));
+        }
+    }
+
+    pub(crate) fn status(&self) -> Option<ThrottlingStatus> {
+        self.state.lock().unwrap().status
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_worst_throttling_status() {
+        let header = "Request Count status: Green (0%), Request Time status: Yellow (52%), Service status: Green (20%)";
+        assert_eq!(header.parse(), Ok(ThrottlingStatus::Yellow));
+        let header = "Request Count status: Red (80%), Request Time status: Green (10%), Service status: Black (100%)";
+        assert_eq!(header.parse(), Ok(ThrottlingStatus::Black));
+        assert_eq!("".parse::<ThrottlingStatus>(), Err(()));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn limits_requests_per_second() {
+        let limiter = RateLimiter::new(RateLimit::default());
+        let start = Instant::now();
+        for _ in 0..5 {
+            limiter.acquire().await;
+        }
+        assert_eq!(start.elapsed(), Duration::ZERO);
+        limiter.acquire().await;
+        assert!(start.elapsed() >= Duration::from_millis(200));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn limits_requests_per_minute() {
+        let limiter = RateLimiter::new(RateLimit {
+            per_second: 100,
+            per_minute: 3,
+        });
+        let start = Instant::now();
+        for _ in 0..4 {
+            limiter.acquire().await;
+        }
+        assert!(start.elapsed() >= Duration::from_secs(20));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn backs_off_on_red_status() {
+        let limiter = RateLimiter::new(RateLimit::default());
+        limiter.observe("Request Count status: Red (85%), Request Time status: Green (0%), Service status: Green (5%)");
+        assert_eq!(limiter.status(), Some(ThrottlingStatus::Red));
+        let start = Instant::now();
+        limiter.acquire().await;
+        assert!(start.elapsed() >= ThrottlingStatus::Red.backoff());
+    }
+}
@@ -0,0 +1,428 @@
+//! PUG View annotations: the Record/Section/Information tree behind compound pages,
+//! plus typed GHS and experimental property extraction.
+
+use serde::Deserialize;
+use std::fmt;
+
+/// Root of the public PUG View service.
+pub const DEFAULT_VIEW_BASE_URL: &str = "https://pubchem.ncbi.nlm.nih.gov/rest/pug_view";
+
+/// PUG View heading holding GHS pictograms, signal word and H/P statements.
+pub const GHS_HEADING: &str = "GHS Classification";
+/// PUG View heading holding measured physical properties.
+pub const EXPERIMENTAL_HEADING: &str = "Experimental Properties";
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct ViewEnvelope {
+    #[serde(rename = "Record")]
+    pub record: ViewRecord,
+}
+
+/// A PUG View record: nested sections of sourced annotations for one compound.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ViewRecord {
+    #[serde(rename = "RecordType")]
+    pub record_type: String,
+    #[serde(rename = "RecordNumber")]
+    pub record_number: u32,
+    #[serde(rename = "RecordTitle")]
+    pub record_title: Option<String>,
+    #[serde(rename = "Section", default)]
+    pub sections: Vec<ViewSection>,
+    #[serde(rename = "Reference", default)]
+    pub references: Vec<ViewReference>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ViewSection {
+    #[serde(rename = "TOCHeading")]
+    pub heading: String,
+    #[serde(rename = "Description")]
+    pub description: Option<String>,
+    #[serde(rename = "Section", default)]
+    pub sections: Vec<ViewSection>,
+    #[serde(rename = "Information", default)]
+    pub information: Vec<ViewInformation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ViewInformation {
+    #[serde(rename = "ReferenceNumber")]
+    pub reference_number: Option<u32>,
+    #[serde(rename = "Name")]
+    pub name: Option<String>,
+    #[serde(rename = "Value")]
+    pub value: ViewValue,
+}
+
+/// An annotation value: marked-up strings, or numbers with an optional unit.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ViewValue {
+    #[serde(rename = "StringWithMarkup", default)]
+    pub strings: Vec<StringWithMarkup>,
+    #[serde(rename = "Number", default)]
+    pub numbers: Vec<f64>,
+    #[serde(rename = "Unit")]
+    pub unit: Option<String>,
+}
+
+impl ViewValue {
+    /// Renders the value as plain text, e.g. `"140 °C"` for a numeric value.
+    pub fn text(&self) -> String {
+        if !self.strings.is_empty() {
+            let strings: Vec<&str> = self.strings.iter().map(|s| s.string.trim()).collect();
+            return strings.join(" ");
+        }
+        let numbers: Vec<String> = self.numbers.iter().map(f64::to_string).collect();
+        match &self.unit {
+            Some(unit) => format!("{} {}", numbers.join(", "), unit),
+            None => numbers.join(", "),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct StringWithMarkup {
+    #[serde(rename = "String")]
+    pub string: String,
+    #[serde(rename = "Markup", default)]
+    pub markup: Vec<Markup>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Markup {
+    #[serde(rename = "Start")]
+    pub start: Option<u32>,
+    #[serde(rename = "Length")]
+    pub length: Option<u32>,
+    #[serde(rename = "URL")]
+    pub url: Option<String>,
+    #[serde(rename = "Type")]
+    pub kind: Option<String>,
+    #[serde(rename = "Extra")]
+    pub extra: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ViewReference {
+    #[serde(rename = "ReferenceNumber")]
+    pub reference_number: u32,
+    #[serde(rename = "SourceName")]
+    pub source_name: Option<String>,
+    #[serde(rename = "SourceID")]
+    pub source_id: Option<String>,
+    #[serde(rename = "URL")]
+    pub url: Option<String>,
+}
+
+impl ViewRecord {
+    /// Every section, at any depth, whose TOC heading matches `heading` (case-insensitive).
+    pub fn find_sections(&self, heading: &str) -> Vec<&ViewSection> {
+        let mut found = Vec::new();
+        for section in &self.sections {
+            section.collect_matching(heading, &mut found);
+        }
+        found
+    }
+
+    /// Name of the source behind a reference number, e.g. `"Hazardous Substances Data Bank (HSDB)"`.
+    pub fn source_name(&self, reference_number: u32) -> Option<&str> {
+        self.references
+            .iter()
+            .find(|reference| reference.reference_number == reference_number)
+            .and_then(|reference| reference.source_name.as_deref())
+    }
+}
+
+impl ViewSection {
+    fn collect_matching<'a>(&'a self, heading: &str, found: &mut Vec<&'a ViewSection>) {
+        if self.heading.eq_ignore_ascii_case(heading) {
+            found.push(self);
+        }
+        for section in &self.sections {
+            section.collect_matching(heading, found);
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pictogram {
+    /// GHS pictogram code, e.g. `GHS07`.
+    pub code: String,
+    /// Pictogram name, e.g. `Irritant`.
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HazardStatement {
+    /// H-code, possibly combined, e.g. `H302` or `H302+H312`.
+    pub code: String,
+    pub text: String,
+}
+
+/// GHS labelling merged across every source that reported it, first source winning on duplicates.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GhsClassification {
+    pub signal: Option<String>,
+    pub pictograms: Vec<Pictogram>,
+    pub hazard_statements: Vec<HazardStatement>,
+    pub precautionary_codes: Vec<String>,
+}
+
+impl GhsClassification {
+    /// Extracts GHS data from every `GHS Classification` section of `record`.
+    pub fn from_record(record: &ViewRecord) -> Self {
+        let mut ghs = GhsClassification::default();
+        let information = record
+            .find_sections(GHS_HEADING)
+            .into_iter()
+            .flat_map(|section| section.information.iter());
+        for info in information {
+            match info.name.as_deref() {
+                Some("Pictogram(s)") => {
+                    for markup in info.value.strings.iter().flat_map(|s| s.markup.iter()) {
+                        let Some(code) = markup.url.as_deref().and_then(pictogram_code) else {
+                            continue;
+                        };
+                        if !ghs.pictograms.iter().any(|p| p.code == code) {
+                            ghs.pictograms.push(Pictogram {
+                                code,
+                                name: markup.extra.clone(),
+                            });
+                        }
+                    }
+                }
+                Some("Signal") if ghs.signal.is_none() => {
+                    ghs.signal = Some(info.value.text()).filter(|signal| !signal.is_empty());
+                }
+                Some("GHS Hazard Statements") => {
+                    for statement in info.value.strings.iter().filter_map(|s| parse_hazard_statement(&s.string)) {
+                        if !ghs.hazard_statements.iter().any(|h| h.code == statement.code) {
+                            ghs.hazard_statements.push(statement);
+                        }
+                    }
+                }
+                Some("Precautionary Statement Codes") => {
+                    for code in info.value.strings.iter().flat_map(|s| precautionary_codes(&s.string)) {
+                        if !ghs.precautionary_codes.contains(&code) {
+                            ghs.precautionary_codes.push(code);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        ghs
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.signal.is_none()
+            && self.pictograms.is_empty()
+            && self.hazard_statements.is_empty()
+            && self.precautionary_codes.is_empty()
+    }
+}
+
+/// `https://.../images/ghs/GHS07.svg` -> `GHS07`.
+fn pictogram_code(url: &str) -> Option<String> {
+    let file = url.rsplit('/').next()?;
+    let code = file.split('.').next()?;
+    code.starts_with("GHS").then(|| code.to_string())
+}
+
+/// Parses `H302 (89.4%): Harmful if swallowed [Warning Acute toxicity, oral]`.
+fn parse_hazard_statement(line: &str) -> Option<HazardStatement> {
+    let line = line.trim();
+    let (head, text) = line.split_once(':')?;
+    let code = head.split_whitespace().next()?;
+    if !code.starts_with('H') || !code[1..].starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+    let text = match text.find(" [") {
+        Some(end) => &text[..end],
+        None => text,
+    };
+    Some(HazardStatement {
+        code: code.to_string(),
+        text: text.trim().to_string(),
+    })
+}
+
+/// Pulls `P264`, `P301+P317`, ... out of a free-text list of P-codes.
+fn precautionary_codes(line: &str) -> Vec<String> {
+    line.split(|c: char| !(c.is_ascii_alphanumeric() || c == '+'))
+        .filter(|token| token.starts_with('P') && token[1..].starts_with(|c: char| c.is_ascii_digit()))
+        .map(String::from)
+        .collect()
+}
+
+impl fmt::Display for GhsClassification {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "GHS Classification:")?;
+        writeln!(f, "---------------------")?;
+        writeln!(f, "Signal: {}", self.signal.as_deref().unwrap_or("None"))?;
+        let pictograms: Vec<String> = self
+            .pictograms
+            .iter()
+            .map(|p| match &p.name {
+                Some(name) => format!("{} ({})", p.code, name),
+                None => p.code.clone(),
+            })
+            .collect();
+        writeln!(f, "Pictograms: {}", pictograms.join(", "))?;
+        writeln!(f, "Hazard Statements:")?;
+        for statement in &self.hazard_statements {
+            writeln!(f, "  {}: {}", statement.code, statement.text)?;
+        }
+        writeln!(f, "Precautionary Codes: {}", self.precautionary_codes.join(", "))
+    }
+}
+
+/// One reported measurement, kept as text since sources mix units and conditions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExperimentalValue {
+    pub value: String,
+    pub source: Option<String>,
+}
+
+/// Measured properties from the `Experimental Properties` section, one entry per source.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExperimentalProperties {
+    pub physical_description: Vec<ExperimentalValue>,
+    pub color: Vec<ExperimentalValue>,
+    pub odor: Vec<ExperimentalValue>,
+    pub boiling_point: Vec<ExperimentalValue>,
+    pub melting_point: Vec<ExperimentalValue>,
+    pub flash_point: Vec<ExperimentalValue>,
+    pub solubility: Vec<ExperimentalValue>,
+    pub density: Vec<ExperimentalValue>,
+    pub vapor_pressure: Vec<ExperimentalValue>,
+    pub log_p: Vec<ExperimentalValue>,
+    pub decomposition: Vec<ExperimentalValue>,
+}
+
+impl ExperimentalProperties {
+    /// Extracts the known headings under every `Experimental Properties` section of `record`.
+    pub fn from_record(record: &ViewRecord) -> Self {
+        let mut properties = ExperimentalProperties::default();
+        for section in record
+            .find_sections(EXPERIMENTAL_HEADING)
+            .into_iter()
+            .flat_map(|section| section.sections.iter())
+        {
+            let Some(field) = properties.field_mut(&section.heading) else {
+                continue;
+            };
+            field.extend(section.information.iter().map(|info| ExperimentalValue {
+                value: info.value.text(),
+                source: info
+                    .reference_number
+                    .and_then(|number| record.source_name(number))
+                    .map(String::from),
+            }));
+        }
+        properties
+    }
+
+    fn field_mut(&mut self, heading: &str) -> Option<&mut Vec<ExperimentalValue>> {
+        match heading {
+            "Physical Description" => Some(&mut self.physical_description),
+            "Color/Form" => Some(&mut self.color),
+            "Odor" => Some(&mut self.odor),
+            "Boiling Point" => Some(&mut self.boiling_point),
+            "Melting Point" => Some(&mut self.melting_point),
+            "Flash Point" => Some(&mut self.flash_point),
+            "Solubility" => Some(&mut self.solubility),
+            "Density" => Some(&mut self.density),
+            "Vapor Pressure" => Some(&mut self.vapor_pressure),
+            "LogP" => Some(&mut self.log_p),
+            "Decomposition" => Some(&mut self.decomposition),
+            _ => None,
+        }
+    }
+
+    fn fields(&self) -> [(&'static str, &Vec<ExperimentalValue>); 11] {
+        [
+            ("Physical Description", &self.physical_description),
+            ("Color/Form", &self.color),
+            ("Odor", &self.odor),
+            ("Boiling Point", &self.boiling_point),
+            ("Melting Point", &self.melting_point),
+            ("Flash Point", &self.flash_point),
+            ("Solubility", &self.solubility),
+            ("Density", &self.density),
+            ("Vapor Pressure", &self.vapor_pressure),
+            ("LogP", &self.log_p),
+            ("Decomposition", &self.decomposition),
+        ]
+    }
+}
+
+impl fmt::Display for ExperimentalProperties {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Experimental Properties:")?;
+        writeln!(f, "---------------------")?;
+        for (heading, values) in self.fields() {
+            if values.is_empty() {
+                continue;
+            }
+            writeln!(f, "{}:", heading)?;
+            for value in values {
+                match &value.source {
+                    Some(source) => writeln!(f, "  {} [{}]", value.value, source)?,
+                    None => writeln!(f, "  {}", value.value)?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(body: &str) -> ViewRecord {
+        serde_json::from_str::<ViewEnvelope>(body).unwrap().record
+    }
+
+    #[test]
+    fn finds_nested_sections_by_heading() {
+        let record = record(include_str!("../tests/data/pug_view_ghs_cid_2244.json"));
+        assert_eq!(record.record_number, 2244);
+        let sections = record.find_sections("ghs classification");
+        assert_eq!(sections.len(), 1);
+        assert_eq!(sections[0].information.len(), 6);
+        assert_eq!(record.source_name(31), Some("Hazardous Substances Data Bank (HSDB)"));
+    }
+
+    #[test]
+    fn extracts_ghs_classification() {
+        let ghs = GhsClassification::from_record(&record(include_str!("../tests/data/pug_view_ghs_cid_2244.json")));
+        assert_eq!(ghs.signal.as_deref(), Some("Warning"));
+        assert_eq!(
+            ghs.pictograms,
+            [Pictogram {
+                code: "GHS07".to_string(),
+                name: Some("Irritant".to_string())
+            }]
+        );
+        let codes: Vec<&str> = ghs.hazard_statements.iter().map(|h| h.code.as_str()).collect();
+        assert_eq!(codes, ["H302", "H315"]);
+        assert_eq!(ghs.hazard_statements[0].text, "Harmful if swallowed");
+        assert_eq!(ghs.precautionary_codes, ["P264", "P270", "P301+P317", "P330", "P501"]);
+    }
+
+    #[test]
+    fn extracts_experimental_properties() {
+        let properties =
+            ExperimentalProperties::from_record(&record(include_str!("../tests/data/pug_view_experimental_cid_2244.json")));
+        assert_eq!(properties.boiling_point.len(), 2);
+        assert_eq!(properties.boiling_point[1].value, "140 °C");
+        assert_eq!(properties.boiling_point[1].source.as_deref(), Some("Hazardous Substances Data Bank (HSDB)"));
+        assert_eq!(properties.melting_point[0].value, "135 °C");
+        assert_eq!(properties.solubility.len(), 2);
+        assert_eq!(properties.log_p[0].value, "1.19");
+        assert!(properties.flash_point.is_empty());
+    }
+}
@@ -0,0 +1,60 @@
+mod common;
+
+use pcpug::{ActivityOutcome, Namespace, PcPugError, PubChemClient};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn fetches_substance_by_sid() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/substance/sid/135196/JSON"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(include_str!("data/substance_sid_135196.json")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let substances = common::client(&server).get_substances(&[135196]).await.unwrap();
+    assert_eq!(substances[0].sid, 135196);
+    assert_eq!(substances[0].cids, [2244]);
+}
+
+#[tokio::test]
+async fn fetches_assay_summaries_by_aid_list() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/assay/aid/1000,1001/summary/JSON"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(include_str!("data/assay_summary_aid_1000.json")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let summaries = common::client(&server).get_assay_summaries(&[1000, 1001]).await.unwrap();
+    assert_eq!(summaries[0].aid, 1000);
+    assert_eq!(summaries[0].targets[0].accession.as_deref(), Some("P23219"));
+}
+
+#[tokio::test]
+async fn fetches_compound_bioactivity_table() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/compound/cid/2244/assaysummary/JSON"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(include_str!("data/assaysummary_cid_2244.json")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let rows = common::client(&server).get_bioactivities(Namespace::Cid, "2244").await.unwrap();
+    let active: Vec<_> = rows.iter().filter(|row| row.outcome == ActivityOutcome::Active).collect();
+    assert_eq!(active.len(), 1);
+    assert_eq!(active[0].target_gene_id, Some(5742));
+}
+
+#[tokio::test]
+async fn rejects_empty_sid_and_aid_lists() {
+    let client = PubChemClient::with_base_url("http://127.0.0.1:9");
+    let err = client.get_substances(&[]).await.unwrap_err();
+    assert!(matches!(err, PcPugError::InvalidInput(_)));
+    let err = client.get_assay_summaries(&[]).await.unwrap_err();
+    assert!(matches!(err, PcPugError::InvalidInput(_)));
+}
@@ -0,0 +1,167 @@
+mod common;
+
+use futures::StreamExt;
+use pcpug::{Namespace, PcPugError, PubChemClient};
+use std::time::{Duration, Instant};
+use wiremock::matchers::{body_string, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const ASPIRIN: &str = include_str!("data/compound_cid_2244.json");
+const L_ALANINE: &str = include_str!("data/compound_cid_5950.json");
+const NOT_FOUND: &str = include_str!("data/fault_not_found.json");
+
+#[tokio::test]
+async fn fetch_many_preserves_input_order() {
+    let server = MockServer::start().await;
+    // The first lookup is the slowest, so completion order differs from input order.
+    Mock::given(method("GET"))
+        .and(path("/compound/name/aspirin/JSON"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(ASPIRIN)
+                .set_delay(Duration::from_millis(300)),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/compound/name/alanine/JSON"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(L_ALANINE))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/compound/name/notacompound/JSON"))
+        .respond_with(ResponseTemplate::new(404).set_body_string(NOT_FOUND))
+        .mount(&server)
+        .await;
+
+    let client = common::client(&server);
+    let names = ["aspirin", "notacompound", "alanine"].map(String::from);
+    let results: Vec<_> = client.fetch_many(Namespace::Name, names, 3).collect().await;
+
+    let identifiers: Vec<&str> = results.iter().map(|(id, _)| id.as_str()).collect();
+    assert_eq!(identifiers, ["aspirin", "notacompound", "alanine"]);
+    assert_eq!(results[0].1.as_ref().unwrap()[0].cid, Some(2244));
+    assert!(results[1].1.as_ref().unwrap_err().is_not_found());
+    assert_eq!(results[2].1.as_ref().unwrap()[0].cid, Some(5950));
+}
+
+#[tokio::test]
+async fn fetch_many_runs_lookups_concurrently() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(ASPIRIN)
+                .set_delay(Duration::from_millis(300)),
+        )
+        .expect(4)
+        .mount(&server)
+        .await;
+
+    let client = common::client(&server);
+    let cids = (1..=4).map(|cid| cid.to_string());
+    let start = Instant::now();
+    let results: Vec<_> = client.fetch_many(Namespace::Cid, cids, 4).collect().await;
+    assert_eq!(results.len(), 4);
+    assert!(results.iter().all(|(_, result)| result.is_ok()));
+    // Sequentially this would take at least 1.2s.
+    assert!(start.elapsed() < Duration::from_millis(1000));
+}
+
+#[tokio::test]
+async fn batch_maps_list_response_back_to_cids() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/compound/cid/JSON"))
+        .and(body_string("cid=5950%2C1%2C2244"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(include_str!("data/compound_cid_2244_5950.json")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::client(&server);
+    let cids = ["5950", "1", "2244", "not-a-cid"].map(String::from);
+    let chunks = client.get_compounds_batch(Namespace::Cid, &cids, 10).await.unwrap();
+
+    assert_eq!(chunks.len(), 1);
+    assert_eq!(chunks[0].identifiers, cids);
+    let results = chunks[0].compounds.as_ref().unwrap();
+    assert_eq!(results.len(), 4);
+    assert_eq!(results[0].as_ref().unwrap().cid, Some(5950));
+    assert!(results[1].is_none());
+    assert_eq!(results[2].as_ref().unwrap().cid, Some(2244));
+    assert!(results[3].is_none());
+}
+
+#[tokio::test]
+async fn batch_splits_into_chunks_and_tolerates_empty_chunks() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/compound/inchikey/JSON"))
+        .and(body_string("inchikey=BSYNRYMUTXBXSQ-UHFFFAOYSA-N%2CQNAYBMKLOCPYGJ-REOHCLBHSA-N"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(include_str!("data/compound_cid_2244_5950.json")))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/compound/inchikey/JSON"))
+        .and(body_string("inchikey=AAAAAAAAAAAAAA-UHFFFAOYSA-N"))
+        .respond_with(ResponseTemplate::new(404).set_body_string(NOT_FOUND))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::client(&server);
+    let keys = [
+        "BSYNRYMUTXBXSQ-UHFFFAOYSA-N",
+        "qnaybmklocpygj-reohclbhsa-n",
+        "AAAAAAAAAAAAAA-UHFFFAOYSA-N",
+    ]
+    .map(String::from);
+    let chunks = client.get_compounds_batch(Namespace::InchiKey, &keys, 2).await.unwrap();
+
+    assert_eq!(chunks.len(), 2);
+    let first = chunks[0].compounds.as_ref().unwrap();
+    assert_eq!(first[0].as_ref().unwrap().cid, Some(2244));
+    assert_eq!(chunks[0].identifiers[1], "qnaybmklocpygj-reohclbhsa-n");
+    assert_eq!(first[1].as_ref().unwrap().cid, Some(5950));
+    assert!(chunks[1].compounds.as_ref().unwrap()[0].is_none());
+}
+
+#[tokio::test]
+async fn batch_keeps_good_chunks_when_one_fails() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/compound/cid/JSON"))
+        .and(body_string("cid=2244%2C5950"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(include_str!("data/compound_cid_2244_5950.json")))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/compound/cid/JSON"))
+        .and(body_string("cid=1"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::client(&server);
+    let cids = ["2244", "5950", "1"].map(String::from);
+    let chunks = client.get_compounds_batch(Namespace::Cid, &cids, 2).await.unwrap();
+
+    assert_eq!(chunks.len(), 2);
+    let good = chunks[0].compounds.as_ref().unwrap();
+    assert_eq!(good[0].as_ref().unwrap().cid, Some(2244));
+    assert_eq!(good[1].as_ref().unwrap().cid, Some(5950));
+    assert_eq!(chunks[1].identifiers, ["1"]);
+    assert!(matches!(chunks[1].compounds, Err(PcPugError::Server { status: 500, .. })));
+}
+
+#[tokio::test]
+async fn batch_rejects_namespaces_without_list_input() {
+    let client = PubChemClient::with_base_url("http://127.0.0.1:9");
+    let names = ["aspirin".to_string()];
+    let err = client.get_compounds_batch(Namespace::Name, &names, 10).await.unwrap_err();
+    assert!(matches!(err, PcPugError::InvalidInput(_)));
+}
@@ -0,0 +1,89 @@
+mod common;
+
+use pcpug::{Namespace, PcPugError, ResponseCache};
+use std::path::PathBuf;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const ASPIRIN: &str = include_str!("data/compound_cid_2244.json");
+
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("pcpug-cache-it-{}-{}", std::process::id(), name));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+#[tokio::test]
+async fn repeated_requests_are_served_from_cache() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/compound/cid/2244/JSON"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(ASPIRIN))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let dir = scratch_dir("repeat");
+    let client = common::with_cache(&server, ResponseCache::new(&dir));
+    let first = client.get_compound_by_cid(2244).await.unwrap();
+    let second = client.get_compound_by_cid(2244).await.unwrap();
+    assert_eq!(first[0].cid, second[0].cid);
+    assert_eq!(ResponseCache::new(&dir).stats().unwrap().entries, 1);
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn posted_queries_are_keyed_by_body() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/compound/smiles/cids/JSON"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"IdentifierList": {"CID": [702]}}"#))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let dir = scratch_dir("post");
+    let client = common::with_cache(&server, ResponseCache::new(&dir));
+    client.get_cids(Namespace::Smiles, "CCO").await.unwrap();
+    client.get_cids(Namespace::Smiles, "CCO").await.unwrap();
+    client.get_cids(Namespace::Smiles, "OCC").await.unwrap();
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn offline_mode_serves_cache_and_fails_on_miss() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/compound/cid/2244/JSON"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(ASPIRIN))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let dir = scratch_dir("offline");
+    common::with_cache(&server, ResponseCache::new(&dir)).get_compound_by_cid(2244).await.unwrap();
+
+    let offline = common::with_cache(&server, ResponseCache::new(&dir).offline(true));
+    assert_eq!(offline.get_compound_by_cid(2244).await.unwrap()[0].cid, Some(2244));
+    let err = offline.get_compound_by_cid(5950).await.unwrap_err();
+    assert!(matches!(err, PcPugError::NotCached(_)));
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn failures_are_not_cached() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/compound/name/nothing/JSON"))
+        .respond_with(ResponseTemplate::new(404).set_body_string(include_str!("data/fault_not_found.json")))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let dir = scratch_dir("failures");
+    let client = common::with_cache(&server, ResponseCache::new(&dir));
+    assert!(client.get_compounds_by_name("nothing").await.unwrap_err().is_not_found());
+    assert!(client.get_compounds_by_name("nothing").await.unwrap_err().is_not_found());
+    assert_eq!(ResponseCache::new(&dir).stats().unwrap().entries, 0);
+    let _ = std::fs::remove_dir_all(dir);
+}
@@ -0,0 +1,193 @@
+use pcpug::{Namespace, PcPugError, PubChemClient, RecordType, RetryPolicy, ThrottlingStatus};
+use wiremock::matchers::{body_string, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const ASPIRIN: &str = include_str!("data/compound_cid_2244.json");
+const ASPIRIN_3D: &str = include_str!("data/compound_cid_2244_3d.json");
+const NOT_FOUND: &str = include_str!("data/fault_not_found.json");
+
+#[tokio::test]
+async fn fetches_compound_by_name_from_base_url() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/compound/name/aspirin/JSON"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(ASPIRIN))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = PubChemClient::with_base_url(server.uri());
+    let compounds = client.get_compounds_by_name("aspirin").await.unwrap();
+    assert_eq!(compounds.len(), 1);
+    assert_eq!(compounds[0].cid, Some(2244));
+    assert_eq!(compounds[0].molecular_formula.as_deref(), Some("C9H8O4"));
+}
+
+#[tokio::test]
+async fn fetches_compound_by_cid() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/compound/cid/2244/JSON"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(ASPIRIN))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = PubChemClient::with_base_url(server.uri());
+    let compounds = client.get_compound_by_cid(2244).await.unwrap();
+    assert_eq!(compounds[0].cid, Some(2244));
+}
+
+#[tokio::test]
+async fn requests_3d_records() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/compound/cid/2244/JSON"))
+        .and(query_param("record_type", "3d"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(ASPIRIN_3D))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = PubChemClient::with_base_url(server.uri()).record_type(RecordType::ThreeD);
+    let compounds = client.get_compound_by_cid(2244).await.unwrap();
+    let conformer = &compounds[0].coords.as_ref().unwrap()[0].conformers[0];
+    assert!(conformer.is_3d());
+    assert_eq!(conformer.id(), Some("000008C400000001"));
+}
+
+#[tokio::test]
+async fn lists_conformer_ids() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/compound/cid/2244/conformers/JSON"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(include_str!("data/conformers_cid_2244.json")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = PubChemClient::with_base_url(server.uri());
+    let conformers = client.get_conformer_ids(Namespace::Cid, "2244").await.unwrap();
+    assert_eq!(conformers[0].cid, 2244);
+    assert_eq!(conformers[0].conformer_ids.len(), 5);
+}
+
+#[tokio::test]
+async fn posts_smiles_as_form_body() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/compound/smiles/JSON"))
+        .and(body_string("smiles=CC%28%3DO%29OC1%3DCC%3DCC%3DC1C%28%3DO%29O"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(ASPIRIN))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = PubChemClient::with_base_url(server.uri());
+    let compounds = client
+        .get_compounds(Namespace::Smiles, "CC(=O)OC1=CC=CC=C1C(=O)O")
+        .await
+        .unwrap();
+    assert_eq!(compounds[0].cid, Some(2244));
+}
+
+#[tokio::test]
+async fn posts_inchi_with_slashes() {
+    let inchi = "InChI=1S/C9H8O4/c1-6(10)13-8-5-3-2-4-7(8)9(11)12/h2-5H,1H3,(H,11,12)";
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/compound/inchi/JSON"))
+        .and(body_string(format!(
+            "inchi={}",
+            "InChI%3D1S%2FC9H8O4%2Fc1-6%2810%2913-8-5-3-2-4-7%288%299%2811%2912%2Fh2-5H%2C1H3%2C%28H%2C11%2C12%29"
+        )))
+        .respond_with(ResponseTemplate::new(200).set_body_string(ASPIRIN))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = PubChemClient::with_base_url(server.uri());
+    let compounds = client.get_compounds(Namespace::Inchi, inchi).await.unwrap();
+    assert_eq!(compounds[0].cid, Some(2244));
+}
+
+#[tokio::test]
+async fn encodes_name_path_segment() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/compound/name/acetylsalicylic%20acid/JSON"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(ASPIRIN))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = PubChemClient::with_base_url(server.uri());
+    let compounds = client.get_compounds_by_name("acetylsalicylic acid").await.unwrap();
+    assert_eq!(compounds[0].cid, Some(2244));
+}
+
+#[tokio::test]
+async fn surfaces_not_found_fault() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/compound/name/notacompound/JSON"))
+        .respond_with(ResponseTemplate::new(404).set_body_string(NOT_FOUND))
+        .mount(&server)
+        .await;
+
+    let client = PubChemClient::with_base_url(server.uri());
+    let err = client.get_compounds_by_name("notacompound").await.unwrap_err();
+    assert!(err.is_not_found());
+    assert_eq!(err.fault().unwrap().message, "No CID found");
+}
+
+#[tokio::test]
+async fn surfaces_server_busy() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(503).set_body_string(
+            r#"{"Fault": {"Code": "PUGREST.ServerBusy", "Message": "Too many requests or server too busy"}}"#,
+        ))
+        .mount(&server)
+        .await;
+
+    let client = PubChemClient::with_base_url(server.uri()).retry_policy(RetryPolicy::none());
+    let err = client.get_compound_by_cid(2244).await.unwrap_err();
+    assert!(matches!(err, PcPugError::ServerBusy(_)));
+}
+
+#[tokio::test]
+async fn surfaces_decode_error_for_unexpected_body() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("<html></html>"))
+        .mount(&server)
+        .await;
+
+    let client = PubChemClient::with_base_url(server.uri());
+    let err = client.get_compound_by_cid(2244).await.unwrap_err();
+    assert!(matches!(err, PcPugError::Decode(_)));
+}
+
+#[tokio::test]
+async fn records_throttling_header() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(ASPIRIN).insert_header(
+            "X-Throttling-Control",
+            "Request Count status: Yellow (60%), Request Time status: Green (10%), Service status: Green (20%)",
+        ))
+        .mount(&server)
+        .await;
+
+    let client = PubChemClient::with_base_url(server.uri());
+    assert_eq!(client.throttling_status(), None);
+    client.get_compound_by_cid(2244).await.unwrap();
+    assert_eq!(client.throttling_status(), Some(ThrottlingStatus::Yellow));
+}
+
+#[test]
+fn trims_trailing_slash_from_base_url() {
+    let client = PubChemClient::with_base_url("http://localhost:8080/rest/pug/");
+    assert_eq!(client.base_url(), "http://localhost:8080/rest/pug");
+}
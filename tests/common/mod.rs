@@ -0,0 +1,38 @@
+//! Client builders shared by the integration tests: no throttling, no retries, and fast ListKey
+//! polling where a test needs it.
+
+// Each test binary compiles its own copy and uses only some of the builders.
+#![allow(dead_code)]
+
+use pcpug::{PollPolicy, PubChemClient, RateLimit, ResponseCache, RetryPolicy};
+use std::time::Duration;
+use wiremock::MockServer;
+
+/// A client for `server` that neither throttles nor retries.
+pub fn client(server: &MockServer) -> PubChemClient {
+    client_at(server.uri())
+}
+
+/// [`client`] rooted at an arbitrary base URL, e.g. a `/rest/pug` path on the mock server.
+pub fn client_at(base_url: impl Into<String>) -> PubChemClient {
+    PubChemClient::with_base_url(base_url)
+        .rate_limit(RateLimit {
+            per_second: 1000,
+            per_minute: 60_000,
+        })
+        .retry_policy(RetryPolicy::none())
+}
+
+/// [`client`] that polls ListKeys every few milliseconds and gives up after `deadline`.
+pub fn with_polling(server: &MockServer, deadline: Duration) -> PubChemClient {
+    client(server).poll_policy(PollPolicy {
+        initial_interval: Duration::from_millis(10),
+        max_interval: Duration::from_millis(40),
+        deadline,
+    })
+}
+
+/// [`client`] that reads and writes `cache`.
+pub fn with_cache(server: &MockServer, cache: ResponseCache) -> PubChemClient {
+    client(server).cache(cache)
+}
@@ -0,0 +1,99 @@
+mod common;
+
+use pcpug::{Namespace, OutputFormat, PcPugError, RecordType};
+use std::time::Duration;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const SDF: &str = "2244\n  -OEChem-10172609372D\n\n 21 21  0     0  0  0  0  0  0999 V2000\nM  END\n$$$$\n";
+const PNG: &[u8] = &[0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0xff, 0xfe];
+
+#[tokio::test]
+async fn downloads_full_record_as_3d_sdf() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/compound/cid/2244/SDF"))
+        .and(query_param("record_type", "3d"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(SDF))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::client(&server).record_type(RecordType::ThreeD);
+    let body = client.download(Namespace::Cid, "2244", None, OutputFormat::Sdf).await.unwrap();
+    assert_eq!(body, SDF.as_bytes());
+}
+
+#[tokio::test]
+async fn keeps_binary_png_bytes_intact() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/compound/name/aspirin/PNG"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(PNG))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let body = common::client(&server)
+        .download(Namespace::Name, "aspirin", None, OutputFormat::Png)
+        .await
+        .unwrap();
+    assert_eq!(body, PNG);
+}
+
+#[tokio::test]
+async fn downloads_property_table_as_csv() {
+    let server = MockServer::start().await;
+    let csv = "\"CID\",\"MolecularWeight\"\n2244,\"180.16\"\n";
+    Mock::given(method("GET"))
+        .and(path("/compound/cid/2244/property/MolecularWeight/CSV"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(csv))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let body = common::client(&server)
+        .download(Namespace::Cid, "2244", Some("property/MolecularWeight"), OutputFormat::Csv)
+        .await
+        .unwrap();
+    assert_eq!(body, csv.as_bytes());
+}
+
+#[tokio::test]
+async fn rejects_tabular_format_for_full_records() {
+    let server = MockServer::start().await;
+    let err = common::client(&server)
+        .download(Namespace::Cid, "2244", None, OutputFormat::Csv)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, PcPugError::InvalidInput(_)));
+}
+
+#[tokio::test]
+async fn downloads_from_listkey_after_waiting() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/compound/formula/C9H8O4/SDF"))
+        .respond_with(ResponseTemplate::new(202).set_body_string(include_str!("data/waiting.json")))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/compound/listkey/2338361557384542337/cids/JSON"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(include_str!("data/cids_formula_c9h8o4.json")))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/compound/listkey/2338361557384542337/SDF"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(SDF))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let body = common::with_polling(&server, Duration::from_secs(5))
+        .download(Namespace::Formula, "C9H8O4", None, OutputFormat::Sdf)
+        .await
+        .unwrap();
+    assert_eq!(body, SDF.as_bytes());
+}
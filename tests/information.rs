@@ -0,0 +1,68 @@
+mod common;
+
+use pcpug::{NameType, Namespace, XrefKind};
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn fetches_synonyms_with_cas_numbers() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/compound/name/aspirin/synonyms/JSON"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(include_str!("data/synonyms_cid_2244.json")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let synonyms = common::client(&server).get_synonyms(Namespace::Name, "aspirin").await.unwrap();
+    assert_eq!(synonyms[0].cid, 2244);
+    assert_eq!(synonyms[0].cas_numbers()[0], "50-78-2");
+}
+
+#[tokio::test]
+async fn fetches_description() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/compound/cid/2244/description/JSON"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(include_str!("data/description_cid_2244.json")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let descriptions = common::client(&server).get_description(Namespace::Cid, "2244").await.unwrap();
+    assert_eq!(descriptions[0].title.as_deref(), Some("Aspirin"));
+    assert_eq!(descriptions[0].descriptions[0].source_name.as_deref(), Some("ChEBI"));
+}
+
+#[tokio::test]
+async fn fetches_requested_xref_types() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/compound/cid/2244/xrefs/RN,PubMedID/JSON"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(include_str!("data/xrefs_cid_2244.json")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let xrefs = common::client(&server)
+        .get_xrefs(Namespace::Cid, "2244", &[XrefKind::RN, XrefKind::PubMedID])
+        .await
+        .unwrap();
+    assert_eq!(xrefs[0].get(XrefKind::RN).len(), 3);
+    assert_eq!(xrefs[0].get(XrefKind::PubMedID).len(), 3);
+}
+
+#[tokio::test]
+async fn lists_cids_by_name_word() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/compound/name/salicylic/cids/JSON"))
+        .and(query_param("name_type", "word"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(include_str!("data/cids_formula_c9h8o4.json")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let cids = common::client(&server).get_cids_by_name("salicylic", NameType::Word).await.unwrap();
+    assert_eq!(cids[0], 2244);
+}
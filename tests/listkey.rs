@@ -0,0 +1,116 @@
+mod common;
+
+use pcpug::{Namespace, PcPugError};
+use std::time::Duration;
+use wiremock::matchers::{body_string, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const WAITING: &str = include_str!("data/waiting.json");
+const CIDS: &str = include_str!("data/cids_formula_c9h8o4.json");
+const ASPIRIN: &str = include_str!("data/compound_cid_2244.json");
+const SUBSTANCE: &str = include_str!("data/substance_sid_135196.json");
+
+#[tokio::test]
+async fn polls_listkey_until_cids_arrive() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/compound/formula/C9H8O4/cids/JSON"))
+        .respond_with(ResponseTemplate::new(202).set_body_string(WAITING))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/compound/listkey/2338361557384542337/cids/JSON"))
+        .respond_with(ResponseTemplate::new(202).set_body_string(WAITING))
+        .up_to_n_times(2)
+        .expect(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/compound/listkey/2338361557384542337/cids/JSON"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(CIDS))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::with_polling(&server, Duration::from_secs(5));
+    let cids = client.get_cids(Namespace::Formula, "C9H8O4").await.unwrap();
+    assert_eq!(cids.len(), 8);
+    assert_eq!(cids[0], 2244);
+}
+
+#[tokio::test]
+async fn formula_lookup_waits_for_records() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/compound/formula/C9H8O4/JSON"))
+        .respond_with(ResponseTemplate::new(202).set_body_string(WAITING))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/compound/listkey/2338361557384542337/JSON"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(ASPIRIN))
+        .mount(&server)
+        .await;
+
+    let client = common::with_polling(&server, Duration::from_secs(5));
+    let compounds = client.get_compounds(Namespace::Formula, "C9H8O4").await.unwrap();
+    assert_eq!(compounds[0].cid, Some(2244));
+}
+
+#[tokio::test]
+async fn batch_chunk_waits_for_records() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/compound/cid/JSON"))
+        .and(body_string("cid=2244"))
+        .respond_with(ResponseTemplate::new(202).set_body_string(WAITING))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/compound/listkey/2338361557384542337/JSON"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(ASPIRIN))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::with_polling(&server, Duration::from_secs(5));
+    let cids = ["2244".to_string()];
+    let chunks = client.get_compounds_batch(Namespace::Cid, &cids, 10).await.unwrap();
+    let compounds = chunks[0].compounds.as_ref().unwrap();
+    assert_eq!(compounds[0].as_ref().unwrap().cid, Some(2244));
+}
+
+#[tokio::test]
+async fn substance_lookup_polls_the_substance_listkey() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/substance/sid/135196/JSON"))
+        .respond_with(ResponseTemplate::new(202).set_body_string(WAITING))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/substance/listkey/2338361557384542337/JSON"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(SUBSTANCE))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::with_polling(&server, Duration::from_secs(5));
+    let substances = client.get_substances(&[135196]).await.unwrap();
+    assert_eq!(substances[0].sid, 135196);
+}
+
+#[tokio::test]
+async fn gives_up_after_deadline() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(202).set_body_string(WAITING))
+        .mount(&server)
+        .await;
+
+    let client = common::with_polling(&server, Duration::from_millis(100));
+    let err = client.get_listkey_cids("2338361557384542337").await.unwrap_err();
+    assert!(matches!(err, PcPugError::Timeout(_)));
+}
@@ -0,0 +1,63 @@
+mod common;
+
+use pcpug::{Namespace, PcPugError, Property, PubChemClient};
+use wiremock::matchers::{body_string, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const TABLE: &str = include_str!("data/property_table_2244_5950.json");
+
+#[tokio::test]
+async fn fetches_property_table_for_cid_list() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/compound/cid/property/MolecularWeight,XLogP/JSON"))
+        .and(body_string("cid=2244%2C5950"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(TABLE))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let cids = ["2244", "5950"].map(String::from);
+    let rows = common::client(&server)
+        .get_properties(Namespace::Cid, &cids, &[Property::MolecularWeight, Property::XLogP])
+        .await
+        .unwrap();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].molecular_weight, Some(180.16));
+    assert_eq!(rows[1].cid, 5950);
+}
+
+#[tokio::test]
+async fn fetches_properties_by_name_and_skips_unknown() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/compound/name/aspirin/property/TPSA/JSON"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            r#"{"PropertyTable": {"Properties": [{"CID": 2244, "TPSA": 63.6}]}}"#,
+        ))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/compound/name/notacompound/property/TPSA/JSON"))
+        .respond_with(ResponseTemplate::new(404).set_body_string(include_str!("data/fault_not_found.json")))
+        .mount(&server)
+        .await;
+
+    let names = ["aspirin", "notacompound"].map(String::from);
+    let rows = common::client(&server)
+        .get_properties(Namespace::Name, &names, &[Property::TPSA])
+        .await
+        .unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].tpsa, Some(63.6));
+}
+
+#[tokio::test]
+async fn rejects_empty_property_list() {
+    let client = PubChemClient::with_base_url("http://127.0.0.1:9");
+    let err = client
+        .get_properties(Namespace::Cid, &["2244".to_string()], &[])
+        .await
+        .unwrap_err();
+    assert!(matches!(err, PcPugError::InvalidInput(_)));
+}
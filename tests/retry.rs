@@ -0,0 +1,87 @@
+use pcpug::{PcPugError, PubChemClient, RetryPolicy};
+use std::time::Duration;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const ASPIRIN: &str = include_str!("data/compound_cid_2244.json");
+const SERVER_BUSY: &str = r#"{"Fault": {"Code": "PUGREST.ServerBusy", "Message": "Too many requests or server too busy"}}"#;
+
+fn quick_retries(max_attempts: u32) -> RetryPolicy {
+    RetryPolicy {
+        max_attempts,
+        initial_backoff: Duration::ZERO,
+        jitter: false,
+        ..RetryPolicy::default()
+    }
+}
+
+#[tokio::test]
+async fn retries_server_busy_until_success() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/compound/cid/2244/JSON"))
+        .respond_with(ResponseTemplate::new(503).set_body_string(SERVER_BUSY))
+        .up_to_n_times(2)
+        .expect(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/compound/cid/2244/JSON"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(ASPIRIN))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = PubChemClient::with_base_url(server.uri()).retry_policy(quick_retries(3));
+    let compounds = client.get_compound_by_cid(2244).await.unwrap();
+    assert_eq!(compounds[0].cid, Some(2244));
+}
+
+#[tokio::test]
+async fn gives_up_after_max_attempts() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(503).set_body_string(SERVER_BUSY))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let client = PubChemClient::with_base_url(server.uri()).retry_policy(quick_retries(2));
+    let err = client.get_compound_by_cid(2244).await.unwrap_err();
+    assert!(matches!(err, PcPugError::ServerBusy(_)));
+}
+
+#[tokio::test]
+async fn does_not_retry_not_found() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(404).set_body_string(include_str!("data/fault_not_found.json")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = PubChemClient::with_base_url(server.uri()).retry_policy(quick_retries(5));
+    let err = client.get_compounds_by_name("notacompound").await.unwrap_err();
+    assert!(err.is_not_found());
+}
+
+#[tokio::test]
+async fn times_out_slow_responses() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(ASPIRIN)
+                .set_delay(Duration::from_secs(5)),
+        )
+        .mount(&server)
+        .await;
+
+    let policy = RetryPolicy {
+        timeout: Some(Duration::from_millis(100)),
+        ..quick_retries(1)
+    };
+    let client = PubChemClient::with_base_url(server.uri()).retry_policy(policy);
+    let err = client.get_compound_by_cid(2244).await.unwrap_err();
+    assert!(matches!(err, PcPugError::Timeout(_)));
+}
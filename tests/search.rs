@@ -0,0 +1,79 @@
+mod common;
+
+use pcpug::{Namespace, PcPugError, PubChemClient, SearchKind, SearchOptions};
+use wiremock::matchers::{body_string, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const CIDS: &str = include_str!("data/cids_formula_c9h8o4.json");
+
+#[tokio::test]
+async fn substructure_search_posts_smiles() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/compound/fastsubstructure/smiles/cids/JSON"))
+        .and(query_param("MaxRecords", "8"))
+        .and(body_string("smiles=c1ccccc1C%28%3DO%29O"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(CIDS))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let options = SearchOptions { max_records: Some(8) };
+    let cids = common::client(&server)
+        .search(SearchKind::Substructure, Namespace::Smiles, "c1ccccc1C(=O)O", options)
+        .await
+        .unwrap();
+    assert_eq!(cids.len(), 8);
+}
+
+#[tokio::test]
+async fn similarity_search_by_cid_sends_threshold() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/compound/fastsimilarity_2d/cid/2244/cids/JSON"))
+        .and(query_param("Threshold", "95"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(CIDS))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let cids = common::client(&server)
+        .search(SearchKind::Similarity { threshold: 95 }, Namespace::Cid, "2244", SearchOptions::default())
+        .await
+        .unwrap();
+    assert_eq!(cids[0], 2244);
+}
+
+#[tokio::test]
+async fn search_compounds_hydrates_hits() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/compound/fastidentity/smiles/cids/JSON"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"IdentifierList": {"CID": [5950, 2244]}}"#))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/compound/cid/JSON"))
+        .and(body_string("cid=5950%2C2244"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(include_str!("data/compound_cid_2244_5950.json")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let compounds = common::client(&server)
+        .search_compounds(SearchKind::Identity, Namespace::Smiles, "C[C@@H](C(=O)O)N", SearchOptions::default())
+        .await
+        .unwrap();
+    let cids: Vec<_> = compounds.iter().map(|c| c.cid).collect();
+    assert_eq!(cids, [Some(5950), Some(2244)]);
+}
+
+#[tokio::test]
+async fn search_rejects_name_input() {
+    let client = PubChemClient::with_base_url("http://127.0.0.1:9");
+    let err = client
+        .search(SearchKind::Substructure, Namespace::Name, "aspirin", SearchOptions::default())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, PcPugError::InvalidInput(_)));
+}
@@ -0,0 +1,67 @@
+mod common;
+
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn fetches_ghs_classification_with_heading_filter() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/rest/pug_view/data/compound/2244/JSON"))
+        .and(query_param("heading", "GHS Classification"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(include_str!("data/pug_view_ghs_cid_2244.json")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let ghs = common::client_at(format!("{}/rest/pug", server.uri())).get_ghs_classification(2244).await.unwrap();
+    assert_eq!(ghs.signal.as_deref(), Some("Warning"));
+    assert_eq!(ghs.pictograms[0].code, "GHS07");
+    assert_eq!(ghs.hazard_statements.len(), 2);
+}
+
+#[tokio::test]
+async fn missing_heading_yields_empty_classification() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/rest/pug_view/data/compound/5950/JSON"))
+        .respond_with(ResponseTemplate::new(404).set_body_string(
+            r#"{"Fault": {"Code": "PUGVIEW.NotFound", "Message": "No data found for this heading"}}"#,
+        ))
+        .mount(&server)
+        .await;
+
+    let ghs = common::client_at(format!("{}/rest/pug", server.uri())).get_ghs_classification(5950).await.unwrap();
+    assert!(ghs.is_empty());
+}
+
+#[tokio::test]
+async fn fetches_experimental_properties() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/rest/pug_view/data/compound/2244/JSON"))
+        .and(query_param("heading", "Experimental Properties"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(include_str!("data/pug_view_experimental_cid_2244.json")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let properties = common::client_at(format!("{}/rest/pug", server.uri())).get_experimental_properties(2244).await.unwrap();
+    assert_eq!(properties.melting_point[0].value, "135 °C");
+    assert_eq!(properties.density[0].value, "1.4 g/cm³");
+}
+
+#[tokio::test]
+async fn view_base_url_overrides_derived_root() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/view/data/compound/2244/JSON"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(include_str!("data/pug_view_ghs_cid_2244.json")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::client_at(format!("{}/rest/pug", server.uri())).view_base_url(format!("{}/view/", server.uri()));
+    let record = client.get_view(2244, None).await.unwrap();
+    assert_eq!(record.record_title.as_deref(), Some("Aspirin"));
+}